@@ -0,0 +1,180 @@
+use crate::roots::{derivative, eval_poly, mul_poly, roots_in};
+use crate::{Point, PolySegment};
+
+///
+/// A point on the curve: index of the segment, parameter on it and the point itself.
+#[derive(Clone, Debug)]
+pub struct CurvePoint {
+  ///
+  /// Index of the segment (the same as the index of its start point in source points).
+  pub segment: usize,
+
+  ///
+  /// Parameter on the segment from `0.0` to `1.0`.
+  pub t: f64,
+
+  ///
+  /// Coordinates of the point.
+  pub point: Point,
+}
+
+///
+/// Kind of the local extremum.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ExtremumKind {
+  ///
+  /// Valley.
+  Min,
+
+  ///
+  /// Peak.
+  Max,
+}
+
+///
+/// Local extremum of one of the coordinates on the curve.
+#[derive(Clone, Debug)]
+pub struct Extremum {
+  ///
+  /// Peak or valley.
+  pub kind: ExtremumKind,
+
+  ///
+  /// Index of the segment.
+  pub segment: usize,
+
+  ///
+  /// Parameter on the segment from `0.0` to `1.0`.
+  pub t: f64,
+
+  ///
+  /// Coordinates of the extremum.
+  pub point: Point,
+}
+
+// Локальные экстремумы одной из координат - нули первой производной.
+// Тип определяется знаком второй производной, если она нулевая - это не экстремум.
+// Начало и конец всей кривой не считаются, а корень в конце сегмента
+// совпадает с корнем в начале следующего, поэтому берётся только `t` из [0, 1).
+fn axis_extrema(segments: &[PolySegment], axis: fn(&PolySegment) -> &[f64; 4]) -> Vec<Extremum> {
+  let mut res = Vec::new();
+
+  for (i, seg) in segments.iter().enumerate() {
+    let d1 = derivative(axis(seg));
+    let d2 = derivative(&d1);
+
+    for t in roots_in(&d1, 0.0, 1.0) {
+      if t >= 1.0 || (i == 0 && t <= 0.0) {
+        continue;
+      }
+      let kind = match eval_poly(&d2, t) {
+        v if v < 0.0 => ExtremumKind::Max,
+        v if v > 0.0 => ExtremumKind::Min,
+        _ => continue,
+      };
+      res.push(Extremum {
+        kind,
+        segment: i,
+        t,
+        point: seg.point_at(t),
+      });
+    }
+  }
+
+  res
+}
+
+///
+/// Returns local extrema (peaks and valleys) of the curve in y-axis.
+///
+/// # Example
+/// ```
+/// use cubic_spline::{extrema_y, ExtremumKind, Points, SplineOpts};
+///
+/// let pts = Points::from(&[(0.0, 0.0), (10.0, 10.0), (20.0, 0.0), (30.0, 10.0)]);
+/// let segments = pts.calc_poly(&SplineOpts::default()).unwrap();
+/// let extrema = extrema_y(&segments);
+///
+/// assert_eq!(extrema.len(), 2);
+/// assert_eq!(extrema[0].kind, ExtremumKind::Max);
+/// assert!(extrema[0].point.approx_eq(&(10.0, 10.0).into()));
+/// assert_eq!(extrema[1].kind, ExtremumKind::Min);
+/// ```
+pub fn extrema_y(segments: &[PolySegment]) -> Vec<Extremum> {
+  axis_extrema(segments, |s| &s.y)
+}
+
+///
+/// Returns local extrema of the curve in x-axis,
+/// i.e. points where the curve turns back horizontally.
+/// `Max` is the rightmost point of the turn, `Min` is the leftmost.
+pub fn extrema_x(segments: &[PolySegment]) -> Vec<Extremum> {
+  axis_extrema(segments, |s| &s.x)
+}
+
+// Знак кривизны: x'y'' - y'x''
+fn curvature_sign(seg: &PolySegment) -> Vec<f64> {
+  let dx = derivative(&seg.x);
+  let dy = derivative(&seg.y);
+  let a = mul_poly(&dx, &derivative(&dy));
+  let b = mul_poly(&dy, &derivative(&dx));
+  a.iter().zip(b.iter()).map(|(a, b)| a - b).collect()
+}
+
+///
+/// Returns inflection points of the curve, where it changes the direction of bending.
+///
+/// Since the curvature may jump at the source points,
+/// a source point is also an inflection point if the curve bends
+/// to different sides before and after it.
+///
+/// # Example
+/// ```
+/// use cubic_spline::{inflections, Points, SplineOpts};
+///
+/// let pts = Points::from(&[(0.0, 0.0), (10.0, 10.0), (20.0, 0.0), (30.0, 10.0)]);
+/// let segments = pts.calc_poly(&SplineOpts::default()).unwrap();
+/// let found = inflections(&segments);
+///
+/// assert_eq!(found.len(), 3);
+/// assert_eq!(found[1].segment, 1);
+/// assert!(found[1].point.approx_eq(&(15.0, 5.0).into()));
+/// ```
+pub fn inflections(segments: &[PolySegment]) -> Vec<CurvePoint> {
+  let mut res = Vec::new();
+  let mut prev_end_sign: Option<f64> = None;
+
+  for (i, seg) in segments.iter().enumerate() {
+    let k = curvature_sign(seg);
+
+    let start = eval_poly(&k, 0.0);
+    if let Some(prev) = prev_end_sign {
+      if prev * start < 0.0 {
+        res.push(CurvePoint {
+          segment: i,
+          t: 0.0,
+          point: seg.point_at(0.0),
+        });
+      }
+    }
+
+    for t in roots_in(&k, 0.0, 1.0) {
+      if t <= 0.0 || t >= 1.0 {
+        continue;
+      }
+      let before = eval_poly(&k, (t - 1e-6).max(0.0));
+      let after = eval_poly(&k, (t + 1e-6).min(1.0));
+      if before * after < 0.0 {
+        res.push(CurvePoint {
+          segment: i,
+          t,
+          point: seg.point_at(t),
+        });
+      }
+    }
+
+    prev_end_sign = Some(eval_poly(&k, 1.0));
+  }
+
+  res
+}
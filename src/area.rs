@@ -0,0 +1,98 @@
+use crate::roots::{derivative, eval_poly, integrate_poly, mul_poly, roots_in};
+use crate::PolySegment;
+
+///
+/// Returns the signed area enclosed by the curve, calculated exactly with Green's theorem.
+///
+/// The area is positive if the curve goes counterclockwise (in coordinate system with y-axis up)
+/// and negative otherwise. If the curve is not closed it is closed with a straight line
+/// from the last point to the first one.
+///
+/// # Example
+/// ```
+/// use cubic_spline::{signed_area, Points, SplineOpts};
+///
+/// let square = Points::from(&[(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0), (0.0, 0.0)]);
+/// let straight = SplineOpts::new().tension(0.0);
+///
+/// let area = signed_area(&square.calc_poly(&straight).unwrap());
+/// assert!((area - 1.0).abs() < 1e-9);
+///
+/// let rounded = signed_area(&square.calc_poly(&SplineOpts::default()).unwrap());
+/// assert!(rounded > 1.0);
+/// ```
+pub fn signed_area(segments: &[PolySegment]) -> f64 {
+  let mut area2 = 0.0;
+
+  for seg in segments {
+    // x * y' - y * x'
+    let a = mul_poly(&seg.x, &derivative(&seg.y));
+    let b = mul_poly(&seg.y, &derivative(&seg.x));
+    let f: Vec<f64> = a.iter().zip(b.iter()).map(|(a, b)| a - b).collect();
+    area2 += integrate_poly(&f, 0.0, 1.0);
+  }
+
+  if let (Some(first), Some(last)) = (segments.first(), segments.last()) {
+    let start = first.point_at(0.0);
+    let end = last.point_at(1.0);
+    area2 += end.x * start.y - end.y * start.x;
+  }
+
+  area2 / 2.0
+}
+
+///
+/// Returns the definite integral of the curve `y dx` from `x_from` to `x_to`,
+/// i.e. the area under the curve, calculated exactly on the polynomial segments.
+///
+/// Only parts of the curve with x-value within the range are taken into account.
+/// If the curve goes backwards in x-axis those parts are subtracted,
+/// as for any line integral. If `x_from > x_to` the result is negated.
+///
+/// # Example
+/// ```
+/// use cubic_spline::{integrate, Points, SplineOpts};
+///
+/// let line = Points::from(&[(0.0, 0.0), (1.0, 1.0), (2.0, 2.0), (3.0, 3.0)]);
+/// let segments = line.calc_poly(&SplineOpts::default()).unwrap();
+///
+/// assert!((integrate(&segments, 0.0, 2.0) - 2.0).abs() < 1e-9);
+/// assert!((integrate(&segments, 2.0, 0.0) + 2.0).abs() < 1e-9);
+/// assert!((integrate(&segments, -5.0, 3.0) - 4.5).abs() < 1e-9);
+/// ```
+pub fn integrate(segments: &[PolySegment], x_from: f64, x_to: f64) -> f64 {
+  let (lo, hi) = if x_from <= x_to {
+    (x_from, x_to)
+  } else {
+    (x_to, x_from)
+  };
+
+  let mut sum = 0.0;
+
+  for seg in segments {
+    let f = mul_poly(&seg.y, &derivative(&seg.x));
+
+    // Границы, где x(t) пересекает `lo` или `hi`, делят сегмент на части,
+    // каждая из которых целиком внутри или целиком снаружи диапазона
+    let mut bounds = vec![0.0];
+    for edge in [lo, hi].iter() {
+      let shifted = [seg.x[0] - edge, seg.x[1], seg.x[2], seg.x[3]];
+      bounds.extend(roots_in(&shifted, 0.0, 1.0));
+    }
+    bounds.push(1.0);
+    bounds.sort_by(f64::total_cmp);
+
+    for w in bounds.windows(2) {
+      let x_mid = eval_poly(&seg.x, 0.5 * (w[0] + w[1]));
+      if x_mid >= lo && x_mid <= hi {
+        sum += integrate_poly(&f, w[0], w[1]);
+      }
+    }
+  }
+
+  if x_from <= x_to {
+    sum
+  } else {
+    -sum
+  }
+}
@@ -0,0 +1,178 @@
+use crate::roots::{derivative, eval_poly, roots_in};
+use crate::{Point, PolySegment};
+
+///
+/// An extreme value of one of the coordinates on the curve and where it occurs.
+#[derive(Clone, Debug)]
+pub struct Extent {
+  ///
+  /// The value of the coordinate.
+  pub value: f64,
+
+  ///
+  /// Index of the segment. Always `0` for a box of a single segment.
+  pub segment: usize,
+
+  ///
+  /// Parameter on the segment from `0.0` to `1.0`.
+  pub t: f64,
+}
+
+///
+/// Exact bounding box of the curve.
+///
+/// The curve bends beyond the passed points,
+/// so the box of the source points may be smaller than the box of the curve.
+///
+/// # Example
+/// ```
+/// use cubic_spline::{Points, SplineOpts};
+///
+/// let pts = Points::from(&[(0.0, 0.0), (10.0, 10.0), (20.0, 5.0)]);
+/// let bbox = pts.bounding_box(&SplineOpts::default()).unwrap();
+///
+/// assert!(bbox.max_y.value > 10.0);
+/// assert_eq!(bbox.max_y.segment, 1);
+/// assert!(bbox.min().approx_eq(&(0.0, 0.0).into()));
+/// ```
+#[derive(Clone, Debug)]
+pub struct BoundingBox {
+  ///
+  /// The leftmost x-value of the curve.
+  pub min_x: Extent,
+
+  ///
+  /// The rightmost x-value of the curve.
+  pub max_x: Extent,
+
+  ///
+  /// The minimum y-value of the curve.
+  pub min_y: Extent,
+
+  ///
+  /// The maximum y-value of the curve.
+  pub max_y: Extent,
+}
+
+impl BoundingBox {
+  ///
+  /// Returns the corner with minimal coordinates.
+  pub fn min(&self) -> Point {
+    Point::new(self.min_x.value, self.min_y.value)
+  }
+
+  ///
+  /// Returns the corner with maximal coordinates.
+  pub fn max(&self) -> Point {
+    Point::new(self.max_x.value, self.max_y.value)
+  }
+
+  ///
+  /// Returns `max_x - min_x`.
+  pub fn width(&self) -> f64 {
+    self.max_x.value - self.min_x.value
+  }
+
+  ///
+  /// Returns `max_y - min_y`.
+  pub fn height(&self) -> f64 {
+    self.max_y.value - self.min_y.value
+  }
+
+  fn merge(&mut self, other: BoundingBox) {
+    if other.min_x.value < self.min_x.value {
+      self.min_x = other.min_x;
+    }
+    if other.max_x.value > self.max_x.value {
+      self.max_x = other.max_x;
+    }
+    if other.min_y.value < self.min_y.value {
+      self.min_y = other.min_y;
+    }
+    if other.max_y.value > self.max_y.value {
+      self.max_y = other.max_y;
+    }
+  }
+}
+
+// Минимум и максимум многочлена на [0, 1]: в концах или в нулях производной
+fn axis_extents(c: &[f64; 4], segment: usize) -> (Extent, Extent) {
+  let mut min = Extent {
+    value: c[0],
+    segment,
+    t: 0.0,
+  };
+  let mut max = min.clone();
+
+  let candidates = roots_in(&derivative(c), 0.0, 1.0);
+  for t in candidates.iter().chain([1.0].iter()) {
+    let value = eval_poly(c, *t);
+    if value < min.value {
+      min = Extent {
+        value,
+        segment,
+        t: *t,
+      };
+    }
+    if value > max.value {
+      max = Extent {
+        value,
+        segment,
+        t: *t,
+      };
+    }
+  }
+
+  (min, max)
+}
+
+impl PolySegment {
+  ///
+  /// Returns exact bounding box of the segment.
+  ///
+  /// # Example
+  /// ```
+  /// use cubic_spline::PolySegment;
+  ///
+  /// // the parabola `y = x^2` for x from -1 to 1
+  /// let seg = PolySegment { x: [-1.0, 2.0, 0.0, 0.0], y: [1.0, -4.0, 4.0, 0.0] };
+  /// let bbox = seg.bounding_box();
+  ///
+  /// assert_eq!(bbox.min_y.value, 0.0);
+  /// assert_eq!(bbox.min_y.t, 0.5);
+  /// assert_eq!(bbox.max_y.value, 1.0);
+  /// ```
+  pub fn bounding_box(&self) -> BoundingBox {
+    self.bounding_box_with_index(0)
+  }
+
+  fn bounding_box_with_index(&self, segment: usize) -> BoundingBox {
+    let (min_x, max_x) = axis_extents(&self.x, segment);
+    let (min_y, max_y) = axis_extents(&self.y, segment);
+    BoundingBox {
+      min_x,
+      max_x,
+      min_y,
+      max_y,
+    }
+  }
+}
+
+///
+/// Returns exact bounding box of the curve represented by segments
+/// or `None` if there are no segments.
+///
+/// [`Points::bounding_box`] does the same for source points and options.
+///
+/// [`Points::bounding_box`]: struct.Points.html#method.bounding_box
+pub fn bounding_box(segments: &[PolySegment]) -> Option<BoundingBox> {
+  let mut iter = segments.iter().enumerate();
+  let (_, first) = iter.next()?;
+  let mut bbox = first.bounding_box_with_index(0);
+
+  for (i, seg) in iter {
+    bbox.merge(seg.bounding_box_with_index(i));
+  }
+
+  Some(bbox)
+}
@@ -0,0 +1,189 @@
+use crate::calc::calc_tangents;
+use crate::{points_iter::PointsIter, Error, Point, Points, Result, SplineOpts};
+
+///
+/// A piece of the curve between two neighbouring points in the form of cubic Bézier curve.
+///
+/// `p0` and `p3` are the points the curve goes through,
+/// `c1` and `c2` are the control points.
+///
+#[derive(Clone, Debug)]
+pub struct BezierSegment {
+  ///
+  /// Start point.
+  pub p0: Point,
+
+  ///
+  /// First control point.
+  pub c1: Point,
+
+  ///
+  /// Second control point.
+  pub c2: Point,
+
+  ///
+  /// End point.
+  pub p3: Point,
+}
+
+impl BezierSegment {
+  ///
+  /// Creates new segment from the start point, two control points and the end point.
+  pub fn new(p0: Point, c1: Point, c2: Point, p3: Point) -> Self {
+    BezierSegment { p0, c1, c2, p3 }
+  }
+
+  ///
+  /// Calculates the point of the segment at parameter `t` (from `0.0` to `1.0`).
+  ///
+  /// # Example
+  /// ```
+  /// use cubic_spline::{BezierSegment, Point};
+  ///
+  /// let seg = BezierSegment::new(
+  ///   Point::new(0.0, 0.0),
+  ///   Point::new(0.0, 1.0),
+  ///   Point::new(1.0, 1.0),
+  ///   Point::new(1.0, 0.0),
+  /// );
+  ///
+  /// assert!(seg.point_at(0.5).approx_eq(&Point::new(0.5, 0.75)));
+  /// ```
+  pub fn point_at(&self, t: f64) -> Point {
+    let mt = 1.0 - t;
+    let b0 = mt * mt * mt;
+    let b1 = 3.0 * mt * mt * t;
+    let b2 = 3.0 * mt * t * t;
+    let b3 = t * t * t;
+
+    Point::new(
+      b0 * self.p0.x + b1 * self.c1.x + b2 * self.c2.x + b3 * self.p3.x,
+      b0 * self.p0.y + b1 * self.c1.y + b2 * self.c2.y + b3 * self.p3.y,
+    )
+  }
+
+  ///
+  /// Calculates the derivative (tangent vector) of the segment at parameter `t`.
+  pub fn derivative_at(&self, t: f64) -> Point {
+    let mt = 1.0 - t;
+    let b0 = 3.0 * mt * mt;
+    let b1 = 6.0 * mt * t;
+    let b2 = 3.0 * t * t;
+
+    Point::new(
+      b0 * (self.c1.x - self.p0.x) + b1 * (self.c2.x - self.c1.x) + b2 * (self.p3.x - self.c2.x),
+      b0 * (self.c1.y - self.p0.y) + b1 * (self.c2.y - self.c1.y) + b2 * (self.p3.y - self.c2.y),
+    )
+  }
+
+  ///
+  /// Splits the segment at parameter `t` into two segments
+  /// that together reproduce the original one exactly.
+  ///
+  /// # Example
+  /// ```
+  /// use cubic_spline::{BezierSegment, Point};
+  ///
+  /// let seg = BezierSegment::new(
+  ///   Point::new(0.0, 0.0),
+  ///   Point::new(0.0, 1.0),
+  ///   Point::new(1.0, 1.0),
+  ///   Point::new(1.0, 0.0),
+  /// );
+  /// let (left, right) = seg.split(0.25);
+  ///
+  /// assert!(left.p3.approx_eq(&seg.point_at(0.25)));
+  /// assert!(left.point_at(0.5).approx_eq(&seg.point_at(0.125)));
+  /// assert!(right.point_at(0.5).approx_eq(&seg.point_at(0.625)));
+  /// ```
+  pub fn split(&self, t: f64) -> (BezierSegment, BezierSegment) {
+    let lerp = |a: &Point, b: &Point| Point::new(a.x + (b.x - a.x) * t, a.y + (b.y - a.y) * t);
+
+    let p01 = lerp(&self.p0, &self.c1);
+    let p12 = lerp(&self.c1, &self.c2);
+    let p23 = lerp(&self.c2, &self.p3);
+    let p012 = lerp(&p01, &p12);
+    let p123 = lerp(&p12, &p23);
+    let mid = lerp(&p012, &p123);
+
+    (
+      BezierSegment::new(self.p0.clone(), p01, p012, mid.clone()),
+      BezierSegment::new(mid, p123, p23, self.p3.clone()),
+    )
+  }
+
+  // Границы выпуклой оболочки: (min_x, min_y, max_x, max_y)
+  pub(crate) fn hull_bounds(&self) -> (f64, f64, f64, f64) {
+    let pts = [&self.p0, &self.c1, &self.c2, &self.p3];
+    pts.iter().fold(
+      (
+        f64::INFINITY,
+        f64::INFINITY,
+        f64::NEG_INFINITY,
+        f64::NEG_INFINITY,
+      ),
+      |(x0, y0, x1, y1), p| (x0.min(p.x), y0.min(p.y), x1.max(p.x), y1.max(p.y)),
+    )
+  }
+}
+
+impl From<[Point; 4]> for BezierSegment {
+  fn from([p0, c1, c2, p3]: [Point; 4]) -> Self {
+    BezierSegment::new(p0, c1, c2, p3)
+  }
+}
+
+impl From<BezierSegment> for [Point; 4] {
+  fn from(seg: BezierSegment) -> Self {
+    [seg.p0, seg.c1, seg.c2, seg.p3]
+  }
+}
+
+///
+/// Returns the curve constructed by [`calc_spline`] as a list of cubic Bézier segments,
+/// one for each pair of neighbouring points.
+/// Tension of points and hidden points from [`SplineOpts`] are taken into account.
+///
+/// Every piece of the curve is represented exactly,
+/// so you can draw it with native Bézier commands of your canvas, PDF or other renderer.
+///
+/// # Example
+/// ```
+/// use cubic_spline::{Points, SplineOpts};
+///
+/// let pts = Points::from(&[(0.0, 0.0), (3.0, 3.0), (6.0, 0.0)]);
+/// let segments = pts.calc_bezier(&SplineOpts::default()).unwrap();
+///
+/// assert_eq!(segments.len(), 2);
+/// assert!(segments[0].c1.approx_eq(&(0.5, 0.5).into()));
+/// assert!(segments[1].p3.approx_eq(&(6.0, 0.0).into()));
+/// ```
+///
+/// [`calc_spline`]: fn.calc_spline.html
+/// [`SplineOpts`]: struct.SplineOpts.html
+pub fn calc_bezier(points: &Points, opts: &SplineOpts) -> Result<Vec<BezierSegment>> {
+  let points_len = points.get_ref().len();
+
+  if points_len < 2 {
+    return Err(Error::TooFewPoints);
+  }
+
+  let tension_from_opt = opts.get_tension();
+  let mut result = Vec::with_capacity(points_len - 1);
+
+  // Каждый эрмитов сегмент из `calc_spline` в точности равен кубической кривой Безье
+  // с контрольными точками `curr + t1 / 3` и `next - t2 / 3`.
+  for pts in PointsIter::new(points, opts) {
+    let (_, curr, next, _) = pts;
+    let ((t1x, t1y), (t2x, t2y)) = calc_tangents(pts, tension_from_opt);
+
+    result.push(BezierSegment::new(
+      Point::new(curr.x, curr.y),
+      Point::new(curr.x + t1x / 3.0, curr.y + t1y / 3.0),
+      Point::new(next.x - t2x / 3.0, next.y - t2y / 3.0),
+      Point::new(next.x, next.y),
+    ));
+  }
+
+  Ok(result)
+}
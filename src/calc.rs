@@ -1,4 +1,5 @@
-use crate::{points_iter::PointsIter, Error, Point, Points, Result, SplineOpts};
+use crate::points_iter::{PointsIter, PointsToCalc};
+use crate::{Error, Point, Points, Result, SplineOpts};
 
 ///
 /// The main function that does all the work.
@@ -33,8 +34,6 @@ pub fn calc_spline(points: &Points, opts: &SplineOpts) -> Result<Points> {
   let tension_from_opt = opts.get_tension();
   let num_of_segments = opts.get_num_of_segments();
 
-  let num_of_segments_f64 = f64::from(num_of_segments);
-
   // количество сегментов на промежутках между точками
   // умноженное на количество промежутков
   // плюс последняя завершающая точка, т.к. функция расчитывает от точки и до точки не включительно
@@ -44,31 +43,8 @@ pub fn calc_spline(points: &Points, opts: &SplineOpts) -> Result<Points> {
 
   let iter = PointsIter::new(points, opts);
 
-  for (prev, curr, next, next2) in iter {
-    let tension = curr.tension.unwrap_or(tension_from_opt);
-
-    let t1x = (next.x - prev.x) * tension;
-    let t2x = (next2.x - curr.x) * tension;
-    let t1y = (next.y - prev.y) * tension;
-    let t2y = (next2.y - curr.y) * tension;
-
-    for t in 0..num_of_segments {
-      let st = f64::from(t) / num_of_segments_f64;
-      let st_pow2 = st.powi(2);
-      let st_pow3 = st.powi(3);
-      let st_pow2x3 = 3.0 * st_pow2;
-      let st_pow3x2 = 2.0 * st_pow3;
-
-      let c1 = st_pow3x2 - st_pow2x3 + 1.0;
-      let c2 = -st_pow3x2 + st_pow2x3;
-      let c3 = st_pow3 - 2.0 * st_pow2 + st;
-      let c4 = st_pow3 - st_pow2;
-
-      let x = c1 * curr.x + c2 * next.x + c3 * t1x + c4 * t2x;
-      let y = c1 * curr.y + c2 * next.y + c3 * t1y + c4 * t2y;
-
-      result.push(Point::new(x, y));
-    }
+  for pts in iter {
+    calc_segment(pts, tension_from_opt, num_of_segments, &mut result);
   }
 
   // проверка лишняя. чтобы не писать unwrap
@@ -80,3 +56,49 @@ pub fn calc_spline(points: &Points, opts: &SplineOpts) -> Result<Points> {
 
   Ok(Points::from(result))
 }
+
+// Точки кривой между `curr` и `next`, включая `curr`, но не включая `next`
+pub(crate) fn calc_segment(
+  pts: PointsToCalc,
+  tension_from_opt: f64,
+  num_of_segments: u32,
+  result: &mut Vec<Point>,
+) {
+  let (_, curr, next, _) = pts;
+  let ((t1x, t1y), (t2x, t2y)) = calc_tangents(pts, tension_from_opt);
+  let num_of_segments_f64 = f64::from(num_of_segments);
+
+  for t in 0..num_of_segments {
+    let st = f64::from(t) / num_of_segments_f64;
+    let st_pow2 = st.powi(2);
+    let st_pow3 = st.powi(3);
+    let st_pow2x3 = 3.0 * st_pow2;
+    let st_pow3x2 = 2.0 * st_pow3;
+
+    let c1 = st_pow3x2 - st_pow2x3 + 1.0;
+    let c2 = -st_pow3x2 + st_pow2x3;
+    let c3 = st_pow3 - 2.0 * st_pow2 + st;
+    let c4 = st_pow3 - st_pow2;
+
+    let x = c1 * curr.x + c2 * next.x + c3 * t1x + c4 * t2x;
+    let y = c1 * curr.y + c2 * next.y + c3 * t1y + c4 * t2y;
+
+    result.push(Point::new(x, y));
+  }
+}
+
+// Касательные в начале (`curr`) и в конце (`next`) сегмента.
+// Натяжение берётся из `curr`, если оно задано, иначе из опций.
+pub(crate) fn calc_tangents(
+  (prev, curr, next, next2): PointsToCalc,
+  tension_from_opt: f64,
+) -> ((f64, f64), (f64, f64)) {
+  let tension = curr.tension.unwrap_or(tension_from_opt);
+
+  let t1x = (next.x - prev.x) * tension;
+  let t2x = (next2.x - curr.x) * tension;
+  let t1y = (next.y - prev.y) * tension;
+  let t2y = (next2.y - curr.y) * tension;
+
+  ((t1x, t1y), (t2x, t2y))
+}
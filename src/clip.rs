@@ -0,0 +1,216 @@
+use crate::roots::roots_in;
+use crate::{calc_bezier, BezierSegment, Point, Points, PolySegment, Result, SplineOpts};
+
+// Куски короче этого (по параметру) считаются касанием границы и отбрасываются
+const MIN_PIECE: f64 = 1e-9;
+
+///
+/// Axis-aligned rectangle to clip the curve with.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Rect {
+  ///
+  /// The left edge.
+  pub min_x: f64,
+
+  ///
+  /// The minimal y-value.
+  pub min_y: f64,
+
+  ///
+  /// The right edge.
+  pub max_x: f64,
+
+  ///
+  /// The maximal y-value.
+  pub max_y: f64,
+}
+
+impl Rect {
+  ///
+  /// Creates new rectangle by two opposite corners in any order.
+  pub fn new(x0: f64, y0: f64, x1: f64, y1: f64) -> Self {
+    Rect {
+      min_x: x0.min(x1),
+      min_y: y0.min(y1),
+      max_x: x0.max(x1),
+      max_y: y0.max(y1),
+    }
+  }
+
+  ///
+  /// Returns `true` if the point is inside the rectangle or on its edge.
+  pub fn contains(&self, p: &Point) -> bool {
+    p.x >= self.min_x && p.x <= self.max_x && p.y >= self.min_y && p.y <= self.max_y
+  }
+
+  fn clamp(&self, p: &mut Point) {
+    p.x = p.x.clamp(self.min_x, self.max_x);
+    p.y = p.y.clamp(self.min_y, self.max_y);
+  }
+}
+
+// Видимый кусок сегмента: (индекс сегмента, t начала, t конца)
+type Piece = (usize, f64, f64);
+
+// Непрерывные видимые участки кривой
+fn visible_runs(segments: &[BezierSegment], rect: &Rect) -> Vec<Vec<Piece>> {
+  let mut runs: Vec<Vec<Piece>> = Vec::new();
+  let mut open = false;
+
+  for (i, seg) in segments.iter().enumerate() {
+    let poly = PolySegment::from(seg);
+
+    let mut cuts = vec![0.0, 1.0];
+    for &(c, edge) in &[
+      (&poly.x, rect.min_x),
+      (&poly.x, rect.max_x),
+      (&poly.y, rect.min_y),
+      (&poly.y, rect.max_y),
+    ] {
+      let shifted = [c[0] - edge, c[1], c[2], c[3]];
+      cuts.extend(roots_in(&shifted, 0.0, 1.0));
+    }
+    cuts.sort_by(|a, b| a.total_cmp(b));
+    cuts.dedup_by(|a, b| (*a - *b).abs() < MIN_PIECE);
+
+    for w in cuts.windows(2) {
+      let (t0, t1) = (w[0], w[1]);
+      if t1 - t0 < MIN_PIECE {
+        continue;
+      }
+
+      if !rect.contains(&seg.point_at((t0 + t1) / 2.0)) {
+        open = false;
+        continue;
+      }
+
+      // продолжаем участок, если предыдущий кусок тоже был видим
+      match runs.last_mut() {
+        Some(run) if open => run.push((i, t0, t1)),
+        _ => runs.push(vec![(i, t0, t1)]),
+      }
+      open = true;
+    }
+  }
+
+  runs
+}
+
+///
+/// Clips the curve with the rectangle.
+/// Returns visible parts of the curve, each one is a continuous run of segments.
+/// Points where the curve enters and leaves the rectangle lie exactly on its edges.
+///
+/// # Example
+/// ```
+/// use cubic_spline::{clip_curve, Points, Rect, SplineOpts};
+///
+/// let pts = Points::from(&[(0.0, 0.0), (1.0, 2.0), (2.0, 0.0), (3.0, 2.0)]);
+/// let segments = pts.calc_bezier(&SplineOpts::default()).unwrap();
+///
+/// // the curve crosses the horizontal band three times
+/// let runs = clip_curve(&segments, &Rect::new(-1.0, 0.5, 4.0, 1.5));
+///
+/// assert_eq!(runs.len(), 3);
+/// assert!((runs[0][0].p0.y - 0.5).abs() < 1e-9);
+/// assert!((runs[0].last().unwrap().p3.y - 1.5).abs() < 1e-9);
+/// ```
+pub fn clip_curve(segments: &[BezierSegment], rect: &Rect) -> Vec<Vec<BezierSegment>> {
+  visible_runs(segments, rect)
+    .into_iter()
+    .map(|run| {
+      let mut res: Vec<BezierSegment> = run
+        .into_iter()
+        .map(|(i, t0, t1)| {
+          let seg = &segments[i];
+          let (_, right) = seg.split(t0);
+          let (mut piece, _) = right.split((t1 - t0) / (1.0 - t0));
+          if t0 == 0.0 {
+            piece.p0 = seg.p0.clone();
+          }
+          if t1 == 1.0 {
+            piece.p3 = seg.p3.clone();
+          }
+          piece
+        })
+        .collect();
+
+      rect.clamp(&mut res[0].p0);
+      if let Some(last) = res.last_mut() {
+        rect.clamp(&mut last.p3);
+      }
+      res
+    })
+    .collect()
+}
+
+///
+/// Calculates the curve like [`calc_spline`] and clips it with the rectangle.
+/// Returns visible parts of the curve as separate runs of points.
+///
+/// Calculated points inside the rectangle are kept as they are
+/// and exact points where the curve crosses the edges are added to the ends of runs.
+///
+/// Returns the same errors as [`calc_spline`].
+///
+/// # Example
+/// ```
+/// use cubic_spline::{clip_spline, Points, Rect, SplineOpts};
+///
+/// let pts = Points::from(&[(0.0, 0.0), (1.0, 2.0), (2.0, 0.0), (3.0, 2.0)]);
+/// let opts = SplineOpts::new().num_of_segments(10);
+///
+/// let runs = clip_spline(&pts, &opts, &Rect::new(-1.0, -1.0, 1.5, 10.0)).unwrap();
+///
+/// assert_eq!(runs.len(), 1);
+/// let last = runs[0].get_ref().last().unwrap();
+/// assert!((last.x - 1.5).abs() < 1e-9);
+/// ```
+///
+/// [`calc_spline`]: fn.calc_spline.html
+pub fn clip_spline(points: &Points, opts: &SplineOpts, rect: &Rect) -> Result<Vec<Points>> {
+  let segments = calc_bezier(points, opts)?;
+  let n = opts.get_num_of_segments() as usize;
+
+  let runs = visible_runs(&segments, rect)
+    .into_iter()
+    .map(|run| {
+      let mut res = Vec::new();
+
+      for (k, &(i, t0, t1)) in run.iter().enumerate() {
+        let seg = &segments[i];
+
+        if k == 0 {
+          let mut start = if t0 == 0.0 {
+            seg.p0.clone()
+          } else {
+            seg.point_at(t0)
+          };
+          rect.clamp(&mut start);
+          res.push(start);
+        }
+
+        res.extend(
+          (1..n)
+            .map(|j| j as f64 / n as f64)
+            .filter(|&t| t - t0 >= MIN_PIECE && t1 - t >= MIN_PIECE)
+            .map(|t| seg.point_at(t)),
+        );
+
+        let end = if t1 == 1.0 {
+          seg.p3.clone()
+        } else {
+          seg.point_at(t1)
+        };
+        res.push(end);
+      }
+
+      if let Some(last) = res.last_mut() {
+        rect.clamp(last);
+      }
+      Points::from(res)
+    })
+    .collect();
+
+  Ok(runs)
+}
@@ -11,6 +11,55 @@ pub enum Error {
   ///
   /// Thrown when there are less than **two** points passed.
   TooFewPoints,
+
+  ///
+  /// Thrown when SVG path data does not start with `M`/`m` command.
+  SvgMissingMoveTo,
+
+  ///
+  /// Thrown when SVG path data contains a command other than `M, L, H, V, C, Q, Z`
+  /// (or their relative versions).
+  SvgUnsupportedCommand,
+
+  ///
+  /// Thrown when SVG path data contains a malformed number
+  /// or a command is missing some of its arguments.
+  SvgInvalidNumber,
+
+  ///
+  /// Thrown when SVG path data contains more than one subpath (`M` command not at the start).
+  SvgMultipleSubpaths,
+
+  ///
+  /// Thrown when samples are not enough to define the fitted curve.
+  FitUnderdetermined,
+
+  ///
+  /// Thrown when x-values of points should be strictly increasing but they are not.
+  NotIncreasingX,
+
+  ///
+  /// Thrown when the number of weights differs from the number of points
+  /// or some weight is not a positive number.
+  InvalidWeights,
+
+  ///
+  /// Thrown when the system of equations of the smoothing spline can not be solved,
+  /// e.g. because of too large or not finite `lambda`, weights or values.
+  SmoothingSingular,
+
+  ///
+  /// Thrown when x-value is outside of the range of points and extrapolation is not allowed.
+  OutOfRange,
+
+  ///
+  /// Thrown when bounds of the range to continue the curve to are not finite numbers.
+  InvalidRange,
+
+  ///
+  /// Thrown when a curve of two points with hidden point at start is reversed.
+  /// Such curve ignores hidden point at end, so the reversed one can not have the same shape.
+  NotReversible,
 }
 
 ///
@@ -24,6 +73,25 @@ impl Error {
     match self {
       Error::MissingY => "Passed values is not even. Last `y` is missing",
       Error::TooFewPoints => "Too few points. There should be more than one",
+      Error::SvgMissingMoveTo => "SVG path data should start with `M` or `m` command",
+      Error::SvgUnsupportedCommand => {
+        "SVG path data contains unsupported command. Only M, L, H, V, C, Q, Z are supported"
+      }
+      Error::SvgInvalidNumber => {
+        "SVG path data contains invalid number or command is missing its arguments"
+      }
+      Error::SvgMultipleSubpaths => "SVG path data should contain only one subpath",
+      Error::FitUnderdetermined => {
+        "Samples are not enough to define the curve. Use fewer knots or more smoothing"
+      }
+      Error::NotIncreasingX => "X-values of points should be strictly increasing",
+      Error::InvalidWeights => "There should be one positive weight for each point",
+      Error::SmoothingSingular => "Equations of the smoothing spline can not be solved",
+      Error::OutOfRange => "X-value is outside of the range of points",
+      Error::InvalidRange => "Bounds of the range should be finite numbers",
+      Error::NotReversible => {
+        "Curve of two points with hidden point at start can not be reversed with the same shape"
+      }
     }
   }
 }
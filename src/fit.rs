@@ -0,0 +1,314 @@
+use crate::calc::calc_tangents;
+use crate::linalg::BandMatrix;
+use crate::points_iter::points_to_calc;
+use crate::roots::roots_in;
+use crate::{Error, Point, Points, Result, SplineOpts};
+
+///
+/// Default number of knots of the fitted curve.
+/// Will be used if not specified in [`FitOpts`].
+///
+/// [`FitOpts`]: struct.FitOpts.html
+pub const DEFAULT_FIT_KNOTS: usize = 10;
+
+///
+/// A list of options indicating how the curve should be fitted to samples.
+///
+/// ```
+/// use cubic_spline::FitOpts;
+///
+/// let opts = FitOpts::new()
+///   .knots(8)
+///   .smoothing(0.1);
+///
+/// assert_eq!(opts.get_knots(), 8);
+/// ```
+/// Options list:
+/// * `knots` -
+///   The number of knots of the fitted curve, at least `2`.
+///   Knots are spread evenly over the x-range of samples.
+///   If not specified [`DEFAULT_FIT_KNOTS`] will be used.
+///
+/// * `smoothing` -
+///   Penalty for bending of the curve (second differences of knot y-values).
+///   `0.0` (default) gives the pure least-squares fit, bigger values give straighter curves.
+///
+/// [`DEFAULT_FIT_KNOTS`]: constant.DEFAULT_FIT_KNOTS.html
+#[derive(Clone, Debug)]
+pub struct FitOpts {
+  knots: usize,
+  smoothing: f64,
+}
+
+impl FitOpts {
+  ///
+  /// Creates new one with defaults.
+  pub fn new() -> Self {
+    FitOpts::default()
+  }
+
+  ///
+  /// Sets knots.
+  pub fn knots(mut self, val: usize) -> Self {
+    self.knots = val;
+    self
+  }
+
+  ///
+  /// Sets smoothing.
+  pub fn smoothing(mut self, val: f64) -> Self {
+    self.smoothing = val;
+    self
+  }
+
+  //
+  // Gets knots.
+  pub fn get_knots(&self) -> usize {
+    self.knots
+  }
+
+  //
+  // Gets smoothing.
+  pub fn get_smoothing(&self) -> f64 {
+    self.smoothing
+  }
+}
+
+impl Default for FitOpts {
+  fn default() -> Self {
+    FitOpts {
+      knots: DEFAULT_FIT_KNOTS,
+      smoothing: 0.0,
+    }
+  }
+}
+
+///
+/// The result of fitting: knots of the curve and how far the samples are from it.
+#[derive(Clone, Debug)]
+pub struct Fit {
+  ///
+  /// Knots of the fitted curve. Pass them to [`calc_spline`] with the same options.
+  ///
+  /// [`calc_spline`]: fn.calc_spline.html
+  pub knots: Points,
+
+  ///
+  /// `sample.y - curve.y` for every sample in the order they were passed.
+  /// `NaN` for samples that were skipped.
+  pub residuals: Vec<f64>,
+
+  ///
+  /// Root mean square of residuals.
+  pub rms: f64,
+
+  ///
+  /// The biggest absolute value of residuals.
+  pub max_residual: f64,
+}
+
+// Откуда берётся точка для расчёта сегмента: узел, y которого ищется, или скрытая точка из опций
+#[derive(Clone, Copy)]
+enum Source {
+  Knot(usize),
+  Fixed(f64),
+}
+
+///
+/// Fits the curve to noisy samples `y = f(x)` in the least-squares sense.
+///
+/// Unlike [`calc_spline`] the curve doesn't pass through every sample.
+/// It is calculated by the returned knots with the same `opts`
+/// (tension and hidden points are taken into account, tensions of samples are ignored).
+/// Samples may go in any order, samples with non-finite coordinates are skipped.
+///
+/// Returns [`Error::TooFewPoints`] if there are less than two knots or usable samples and
+/// [`Error::FitUnderdetermined`] if samples don't define the curve
+/// (e.g. all of them have the same x or there are knots without samples around them
+/// and `smoothing` is `0.0`).
+///
+/// # Example
+/// ```
+/// use cubic_spline::{fit_spline, FitOpts, Points, SplineOpts};
+///
+/// let samples: Vec<(f64, f64)> = (0..100)
+///   .map(|i| {
+///     let x = i as f64 / 10.0;
+///     let noise = if i % 2 == 0 { 0.1 } else { -0.1 };
+///     (x, x.sin() + noise)
+///   })
+///   .collect();
+///
+/// let opts = SplineOpts::default();
+/// let fit = fit_spline(&Points::from(&samples), &opts, &FitOpts::new().knots(8)).unwrap();
+///
+/// assert_eq!(fit.knots.get_ref().len(), 8);
+/// assert!(fit.rms < 0.12);
+///
+/// let curve = fit.knots.calc_spline(&opts).unwrap();
+/// ```
+///
+/// [`calc_spline`]: fn.calc_spline.html
+/// [`Error::TooFewPoints`]: enum.Error.html#variant.TooFewPoints
+/// [`Error::FitUnderdetermined`]: enum.Error.html#variant.FitUnderdetermined
+pub fn fit_spline(samples: &Points, opts: &SplineOpts, fit_opts: &FitOpts) -> Result<Fit> {
+  let n = fit_opts.get_knots();
+  let samples = samples.get_ref();
+  let usable: Vec<usize> = (0..samples.len())
+    .filter(|&i| samples[i].x.is_finite() && samples[i].y.is_finite())
+    .collect();
+
+  if n < 2 || usable.len() < 2 {
+    return Err(Error::TooFewPoints);
+  }
+
+  let (min_x, max_x) = usable
+    .iter()
+    .fold((f64::INFINITY, f64::NEG_INFINITY), |acc, &i| {
+      (acc.0.min(samples[i].x), acc.1.max(samples[i].x))
+    });
+  if min_x == max_x {
+    return Err(Error::FitUnderdetermined);
+  }
+
+  let step = (max_x - min_x) / (n - 1) as f64;
+  let knot_xs: Vec<f64> = (0..n).map(|j| min_x + step * j as f64).collect();
+  let tension = opts.get_tension();
+
+  // Кривая линейно зависит от y узлов: y(sample) = Σ w_j * y_j + c.
+  // Для каждого сэмпла находим сегмент и параметр `t` по его x и веса узлов.
+  let x_knots = Points::from(knot_xs.iter().map(|&x| (x, 0.0)).collect::<Vec<_>>());
+  let rows: Vec<(usize, [(Source, f64); 4])> = usable
+    .iter()
+    .map(|&i| {
+      let x = samples[i].x;
+      let k = (((x - min_x) / step).floor().max(0.0) as usize).min(n - 2);
+      let t = param_at_x(x_knots.get_ref(), opts, k, x);
+      (i, weights(opts, n, k, t, tension))
+    })
+    .collect();
+
+  let mut a = BandMatrix::new(n, 3);
+  let mut rhs = vec![0.0; n];
+
+  for (i, row) in &rows {
+    let fixed: f64 = row
+      .iter()
+      .filter_map(|(s, w)| match s {
+        Source::Fixed(y) => Some(w * y),
+        Source::Knot(_) => None,
+      })
+      .sum();
+    let target = samples[*i].y - fixed;
+
+    for (s1, w1) in row {
+      if let Source::Knot(j1) = s1 {
+        rhs[*j1] += w1 * target;
+        for (s2, w2) in row {
+          if let Source::Knot(j2) = s2 {
+            if j1 >= j2 {
+              a.add(*j1, *j2, w1 * w2);
+            }
+          }
+        }
+      }
+    }
+  }
+
+  let smoothing = fit_opts.get_smoothing();
+  if smoothing > 0.0 {
+    // штраф λ·Σ (y[j-1] - 2·y[j] + y[j+1])²
+    let d = [1.0, -2.0, 1.0];
+    for j in 1..n - 1 {
+      for p in 0..3 {
+        for q in 0..=p {
+          a.add(j - 1 + p, j - 1 + q, smoothing * d[p] * d[q]);
+        }
+      }
+    }
+  }
+
+  let ys = a.solve(rhs).ok_or(Error::FitUnderdetermined)?;
+
+  let mut residuals = vec![f64::NAN; samples.len()];
+  for (i, row) in &rows {
+    let y: f64 = row
+      .iter()
+      .map(|(s, w)| match s {
+        Source::Knot(j) => w * ys[*j],
+        Source::Fixed(y) => w * y,
+      })
+      .sum();
+    residuals[*i] = samples[*i].y - y;
+  }
+
+  let (sum_sq, max_residual) = rows.iter().fold((0.0, 0.0f64), |(s, m), (i, _)| {
+    let r = residuals[*i];
+    (s + r * r, m.max(r.abs()))
+  });
+
+  Ok(Fit {
+    knots: Points::from(
+      knot_xs
+        .into_iter()
+        .zip(ys)
+        .map(|(x, y)| Point::new(x, y))
+        .collect::<Vec<_>>(),
+    ),
+    residuals,
+    rms: (sum_sq / rows.len() as f64).sqrt(),
+    max_residual,
+  })
+}
+
+// Параметр `t` на сегменте `k`, при котором кривая достигает `x`
+fn param_at_x(x_knots: &[Point], opts: &SplineOpts, k: usize, x: f64) -> f64 {
+  let pts = points_to_calc(x_knots, opts, k);
+  let (curr, next) = (pts.1.x, pts.2.x);
+  let ((t1, _), (t2, _)) = calc_tangents(pts, opts.get_tension());
+
+  let c = [
+    curr - x,
+    t1,
+    -3.0 * curr + 3.0 * next - 2.0 * t1 - t2,
+    2.0 * curr - 2.0 * next + t1 + t2,
+  ];
+  match roots_in(&c, 0.0, 1.0).first() {
+    Some(t) => *t,
+    None if (x - curr).abs() <= (x - next).abs() => 0.0,
+    None => 1.0,
+  }
+}
+
+// Веса точек `prev, curr, next, next2` в значении кривой на сегменте `k` при параметре `t`
+fn weights(opts: &SplineOpts, n: usize, k: usize, t: f64, tension: f64) -> [(Source, f64); 4] {
+  let (t2, t3) = (t * t, t * t * t);
+  let h00 = 2.0 * t3 - 3.0 * t2 + 1.0;
+  let h01 = -2.0 * t3 + 3.0 * t2;
+  let h10 = t3 - 2.0 * t2 + t;
+  let h11 = t3 - t2;
+
+  let prev = if k > 0 {
+    Source::Knot(k - 1)
+  } else {
+    opts
+      .get_hidden_point_at_start()
+      .map_or(Source::Knot(k), |p| Source::Fixed(p.y))
+  };
+  let next2 = if k + 2 < n {
+    Source::Knot(k + 2)
+  } else if n == 2 {
+    Source::Knot(k + 1)
+  } else {
+    opts
+      .get_hidden_point_at_end()
+      .map_or(Source::Knot(k + 1), |p| Source::Fixed(p.y))
+  };
+
+  [
+    (prev, -tension * h10),
+    (Source::Knot(k), h00 - tension * h11),
+    (Source::Knot(k + 1), h01 + tension * h10),
+    (next2, tension * h11),
+  ]
+}
@@ -0,0 +1,528 @@
+use crate::calc::calc_tangents;
+use crate::points_iter::points_to_calc;
+use crate::{BezierSegment, Point, Points, SplineOpts, DEFAULT_TENSION};
+use std::f64::consts::PI;
+
+// Поворот направления больше этого угла считается углом (изломом) штриха
+const CORNER_ANGLE: f64 = PI / 3.0;
+// Если ошибка меньше `tolerance * REPARAM_FACTOR`, сначала пробуем уточнить параметры, а не делить
+const REPARAM_FACTOR: f64 = 4.0;
+const MAX_REPARAM: usize = 4;
+
+type Vec2 = (f64, f64);
+
+fn sub(a: &Point, b: &Point) -> Vec2 {
+  (a.x - b.x, a.y - b.y)
+}
+
+fn dot(a: Vec2, b: Vec2) -> f64 {
+  a.0 * b.0 + a.1 * b.1
+}
+
+fn len(a: Vec2) -> f64 {
+  a.0.hypot(a.1)
+}
+
+fn normalize(a: Vec2) -> Vec2 {
+  let l = len(a);
+  if l == 0.0 {
+    a
+  } else {
+    (a.0 / l, a.1 / l)
+  }
+}
+
+fn offset(p: &Point, v: Vec2, k: f64) -> Point {
+  Point::new(p.x + v.0 * k, p.y + v.1 * k)
+}
+
+fn dist(a: &Point, b: &Point) -> f64 {
+  len(sub(a, b))
+}
+
+///
+/// Fits cubic Bézier curves to a dense polyline, e.g. raw pointer samples of a freehand stroke
+/// (Philip J. Schneider, "An Algorithm for Automatically Fitting Digitized Curves").
+///
+/// Returns as few segments as it can so that every point of the polyline is not farther
+/// than `tolerance` from the curve. Sharp turns of the polyline are detected
+/// and kept as corners, elsewhere the curve is smooth.
+///
+/// Returns an empty `Vec` if there are less than two distinct points.
+///
+/// # Example
+/// ```
+/// use cubic_spline::{fit_bezier, Points};
+///
+/// // a quarter of a circle followed by a straight line with a corner between them
+/// let mut samples: Vec<(f64, f64)> = (0..=50)
+///   .map(|i| {
+///     let a = std::f64::consts::FRAC_PI_2 * i as f64 / 50.0;
+///     (100.0 * a.cos(), 100.0 * a.sin())
+///   })
+///   .collect();
+/// samples.extend((1..=50).map(|i| (2.0 * i as f64, 100.0)));
+///
+/// let segments = fit_bezier(&Points::from(&samples), 0.5);
+///
+/// assert!(segments.len() <= 4);
+/// assert!(segments.iter().any(|s| s.p3.approx_eq(&(0.0, 100.0).into())));
+/// ```
+pub fn fit_bezier(points: &Points, tolerance: f64) -> Vec<BezierSegment> {
+  let pts = dedup(points.get_ref());
+  if pts.len() < 2 {
+    return Vec::new();
+  }
+
+  fit_segments(&pts, &corners(&pts, tolerance * 2.0), tolerance)
+    .into_iter()
+    .map(|(seg, _)| seg)
+    .collect()
+}
+
+// Сегменты вместе с индексами их конечных точек
+fn fit_segments(
+  pts: &[Point],
+  corner_idx: &[usize],
+  tolerance: f64,
+) -> Vec<(BezierSegment, usize)> {
+  if pts.len() < 2 {
+    return Vec::new();
+  }
+
+  let window = tolerance * 2.0;
+  let mut result = Vec::new();
+  let mut bounds = corner_idx.to_vec();
+  bounds.insert(0, 0);
+  bounds.push(pts.len() - 1);
+
+  for w in bounds.windows(2) {
+    let part = &pts[w[0]..=w[1]];
+    let t1 = tangent_forward(part, 0, window);
+    let t2 = tangent_backward(part, part.len() - 1, window);
+    fit_cubic(part, w[0], t1, t2, tolerance, &mut result);
+  }
+
+  result
+}
+
+///
+/// Fits the curve to a dense polyline like [`fit_bezier`] does,
+/// but returns it as knots with tensions for [`calc_spline`].
+///
+/// Each knot except the last one has its own tension, so the curve should be calculated
+/// with options without hidden points (tension from options is not used).
+/// Corners are represented by a repeated knot with zero tension between the copies.
+/// Every point of the polyline is not farther than `tolerance` from the resulting curve,
+/// though it usually needs a few more knots than [`fit_bezier`] needs segments.
+///
+/// # Example
+/// ```
+/// use cubic_spline::{fit_knots, Points, SplineOpts};
+///
+/// let samples: Vec<(f64, f64)> = (0..=100)
+///   .map(|i| {
+///     let x = i as f64;
+///     (x, 20.0 * (x / 15.0).sin())
+///   })
+///   .collect();
+///
+/// let knots = fit_knots(&Points::from(&samples), 0.5);
+/// assert!(knots.get_ref().len() < 20);
+///
+/// let curve = knots.calc_spline(&SplineOpts::default()).unwrap();
+/// ```
+///
+/// [`fit_bezier`]: fn.fit_bezier.html
+/// [`calc_spline`]: fn.calc_spline.html
+pub fn fit_knots(points: &Points, tolerance: f64) -> Points {
+  let pts = dedup(points.get_ref());
+  if pts.len() < 2 {
+    return Points::from(pts);
+  }
+
+  let corner_idx = corners(&pts, tolerance * 2.0);
+
+  // Начинаем с концов сегментов Безье, затем добавляем узлы туда, где ошибка слишком велика
+  let mut knots: Vec<usize> = vec![0];
+  knots.extend(
+    fit_segments(&pts, &corner_idx, tolerance)
+      .into_iter()
+      .map(|(_, i)| i),
+  );
+
+  loop {
+    let (result, splits) = fit_tensions(&pts, &knots, &corner_idx, tolerance);
+    if splits.is_empty() {
+      return result;
+    }
+    knots.extend(splits);
+    knots.sort_unstable();
+    knots.dedup();
+  }
+}
+
+// Подбирает натяжения при заданных узлах (индексах точек).
+// Возвращает узлы с натяжениями и точки, в которых нужно добавить узлы.
+fn fit_tensions(
+  pts: &[Point],
+  knots: &[usize],
+  corner_idx: &[usize],
+  tolerance: f64,
+) -> (Points, Vec<usize>) {
+  // Угол задаётся повтором узла, сегмент между копиями вырождается в точку при нулевом натяжении
+  let mut knot_pts = Vec::with_capacity(knots.len());
+  let mut ranges = Vec::with_capacity(knots.len());
+  for (k, &i) in knots.iter().enumerate() {
+    knot_pts.push(pts[i].clone());
+    if let Some(&next) = knots.get(k + 1) {
+      ranges.push(Some((i, next)));
+      let is_corner = k > 0 && corner_idx.binary_search(&i).is_ok();
+      if is_corner {
+        let last = knot_pts.len() - 1;
+        knot_pts[last].tension = Some(0.0);
+        knot_pts.push(pts[i].clone());
+        ranges.insert(ranges.len() - 1, None);
+      }
+    }
+  }
+
+  let opts = SplineOpts::default();
+  let mut splits = Vec::new();
+  let mut tensions = Vec::with_capacity(ranges.len());
+
+  for (k, range) in ranges.iter().enumerate() {
+    let (from, to) = match range {
+      Some(r) => *r,
+      None => {
+        tensions.push(0.0);
+        continue;
+      }
+    };
+
+    let (prev, curr, next, next2) = points_to_calc(&knot_pts, &opts, k);
+    let ((d1x, d1y), (d2x, d2y)) = calc_tangents((prev, curr, next, next2), 1.0);
+    let inner = &pts[from + 1..to];
+    if inner.is_empty() {
+      tensions.push(DEFAULT_TENSION);
+      continue;
+    }
+
+    let eval = |tension: f64, u: f64| hermite(curr, next, (d1x, d1y), (d2x, d2y), tension, u);
+
+    let mut u = chord_params(&pts[from..=to]);
+    let mut tension = DEFAULT_TENSION;
+    for _ in 0..=MAX_REPARAM {
+      tension = best_tension(
+        curr,
+        next,
+        (d1x, d1y),
+        (d2x, d2y),
+        inner,
+        &u[1..u.len() - 1],
+      );
+      for (j, p) in inner.iter().enumerate() {
+        u[j + 1] = newton_step(|t| eval(tension, t), p, u[j + 1]);
+      }
+    }
+
+    let (max_err, at) = inner
+      .iter()
+      .enumerate()
+      .map(|(j, p)| (dist(&eval(tension, u[j + 1]).0, p), from + 1 + j))
+      .fold((0.0, from), |acc, e| if e.0 > acc.0 { e } else { acc });
+
+    if max_err > tolerance {
+      splits.push(at);
+    }
+    tensions.push(tension);
+  }
+
+  for (p, t) in knot_pts.iter_mut().zip(tensions) {
+    p.tension = Some(t);
+  }
+  (Points::from(knot_pts), splits)
+}
+
+// Точка, первая и вторая производные эрмитова сегмента с касательными `tension * d1` и `tension * d2`
+fn hermite(
+  p0: &Point,
+  p1: &Point,
+  d1: Vec2,
+  d2: Vec2,
+  tension: f64,
+  t: f64,
+) -> (Point, Vec2, Vec2) {
+  let (t2, t3) = (t * t, t * t * t);
+  let h = [
+    2.0 * t3 - 3.0 * t2 + 1.0,
+    -2.0 * t3 + 3.0 * t2,
+    t3 - 2.0 * t2 + t,
+    t3 - t2,
+  ];
+  let dh = [
+    6.0 * t2 - 6.0 * t,
+    -6.0 * t2 + 6.0 * t,
+    3.0 * t2 - 4.0 * t + 1.0,
+    3.0 * t2 - 2.0 * t,
+  ];
+  let ddh = [
+    12.0 * t - 6.0,
+    -12.0 * t + 6.0,
+    6.0 * t - 4.0,
+    6.0 * t - 2.0,
+  ];
+
+  let comb = |h: &[f64; 4]| {
+    (
+      h[0] * p0.x + h[1] * p1.x + tension * (h[2] * d1.0 + h[3] * d2.0),
+      h[0] * p0.y + h[1] * p1.y + tension * (h[2] * d1.1 + h[3] * d2.1),
+    )
+  };
+  let (x, y) = comb(&h);
+  (Point::new(x, y), comb(&dh), comb(&ddh))
+}
+
+// Натяжение, при котором сумма квадратов отклонений точек минимальна (кривая линейна по натяжению)
+fn best_tension(p0: &Point, p1: &Point, d1: Vec2, d2: Vec2, inner: &[Point], u: &[f64]) -> f64 {
+  let (num, den) = inner.iter().zip(u).fold((0.0, 0.0), |(num, den), (p, &t)| {
+    let (base, _, _) = hermite(p0, p1, d1, d2, 0.0, t);
+    let (t2, t3) = (t * t, t * t * t);
+    let (h10, h11) = (t3 - 2.0 * t2 + t, t3 - t2);
+    let v = (h10 * d1.0 + h11 * d2.0, h10 * d1.1 + h11 * d2.1);
+    let r = sub(p, &base);
+    (num + dot(r, v), den + dot(v, v))
+  });
+
+  if den == 0.0 {
+    DEFAULT_TENSION
+  } else {
+    (num / den).max(0.0)
+  }
+}
+
+// `base` - индекс первой точки `pts` в исходной полилинии
+fn fit_cubic(
+  pts: &[Point],
+  base: usize,
+  t1: Vec2,
+  t2: Vec2,
+  tolerance: f64,
+  result: &mut Vec<(BezierSegment, usize)>,
+) {
+  let first = &pts[0];
+  let last = &pts[pts.len() - 1];
+  let end = base + pts.len() - 1;
+
+  if pts.len() == 2 {
+    let d = dist(first, last) / 3.0;
+    let seg = BezierSegment::new(
+      first.clone(),
+      offset(first, t1, d),
+      offset(last, t2, d),
+      last.clone(),
+    );
+    result.push((seg, end));
+    return;
+  }
+
+  let mut u = chord_params(pts);
+  let mut seg = generate_bezier(pts, &u, t1, t2);
+  let (mut max_err, mut split) = max_error(pts, &seg, &u);
+  if max_err <= tolerance {
+    result.push((seg, end));
+    return;
+  }
+
+  if max_err <= tolerance * REPARAM_FACTOR {
+    for _ in 0..MAX_REPARAM {
+      for (p, t) in pts.iter().zip(u.iter_mut()) {
+        *t = newton_step(
+          |t| {
+            let d = seg.derivative_at(t);
+            (seg.point_at(t), (d.x, d.y), second_derivative(&seg, t))
+          },
+          p,
+          *t,
+        );
+      }
+      seg = generate_bezier(pts, &u, t1, t2);
+      let (e, s) = max_error(pts, &seg, &u);
+      max_err = e;
+      split = s;
+      if max_err <= tolerance {
+        result.push((seg, end));
+        return;
+      }
+    }
+  }
+
+  // Делим в точке наибольшей ошибки, касательная там общая для обеих частей
+  let center = normalize(sub(&pts[split - 1], &pts[split + 1]));
+  fit_cubic(&pts[..=split], base, t1, center, tolerance, result);
+  let center = (-center.0, -center.1);
+  fit_cubic(&pts[split..], base + split, center, t2, tolerance, result);
+}
+
+// Метод наименьших квадратов для длин касательных при заданных направлениях
+fn generate_bezier(pts: &[Point], u: &[f64], t1: Vec2, t2: Vec2) -> BezierSegment {
+  let first = &pts[0];
+  let last = &pts[pts.len() - 1];
+
+  let mut c = [[0.0; 2]; 2];
+  let mut x = [0.0; 2];
+  for (p, &t) in pts.iter().zip(u) {
+    let mt = 1.0 - t;
+    let (b0, b1, b2, b3) = (mt * mt * mt, 3.0 * mt * mt * t, 3.0 * mt * t * t, t * t * t);
+    let a1 = (t1.0 * b1, t1.1 * b1);
+    let a2 = (t2.0 * b2, t2.1 * b2);
+
+    c[0][0] += dot(a1, a1);
+    c[0][1] += dot(a1, a2);
+    c[1][1] += dot(a2, a2);
+
+    let tmp = (
+      p.x - (first.x * (b0 + b1) + last.x * (b2 + b3)),
+      p.y - (first.y * (b0 + b1) + last.y * (b2 + b3)),
+    );
+    x[0] += dot(a1, tmp);
+    x[1] += dot(a2, tmp);
+  }
+  c[1][0] = c[0][1];
+
+  let det = c[0][0] * c[1][1] - c[1][0] * c[0][1];
+  let (mut alpha1, mut alpha2) = if det == 0.0 {
+    (0.0, 0.0)
+  } else {
+    (
+      (x[0] * c[1][1] - x[1] * c[0][1]) / det,
+      (c[0][0] * x[1] - c[1][0] * x[0]) / det,
+    )
+  };
+
+  // Слишком короткие или направленные назад касательные заменяем эвристикой
+  let seg_len = dist(first, last);
+  let eps = seg_len * 1e-6;
+  if alpha1 < eps || alpha2 < eps {
+    alpha1 = seg_len / 3.0;
+    alpha2 = alpha1;
+  }
+
+  BezierSegment::new(
+    first.clone(),
+    offset(first, t1, alpha1),
+    offset(last, t2, alpha2),
+    last.clone(),
+  )
+}
+
+fn max_error(pts: &[Point], seg: &BezierSegment, u: &[f64]) -> (f64, usize) {
+  let mut res = (0.0, pts.len() / 2);
+  for i in 1..pts.len() - 1 {
+    let d = dist(&seg.point_at(u[i]), &pts[i]);
+    if d > res.0 {
+      res = (d, i);
+    }
+  }
+  res
+}
+
+fn second_derivative(seg: &BezierSegment, t: f64) -> Vec2 {
+  let mt = 1.0 - t;
+  let a = (
+    seg.c2.x - 2.0 * seg.c1.x + seg.p0.x,
+    seg.c2.y - 2.0 * seg.c1.y + seg.p0.y,
+  );
+  let b = (
+    seg.p3.x - 2.0 * seg.c2.x + seg.c1.x,
+    seg.p3.y - 2.0 * seg.c2.y + seg.c1.y,
+  );
+  (6.0 * (mt * a.0 + t * b.0), 6.0 * (mt * a.1 + t * b.1))
+}
+
+// Один шаг Ньютона для ближайшей к `p` точки кривой
+fn newton_step<F: Fn(f64) -> (Point, Vec2, Vec2)>(curve: F, p: &Point, t: f64) -> f64 {
+  let (q, d1, d2) = curve(t);
+  let diff = sub(&q, p);
+  let num = dot(diff, d1);
+  let den = dot(d1, d1) + dot(diff, d2);
+  if den == 0.0 {
+    return t;
+  }
+  (t - num / den).clamp(0.0, 1.0)
+}
+
+fn chord_params(pts: &[Point]) -> Vec<f64> {
+  let mut u = Vec::with_capacity(pts.len());
+  u.push(0.0);
+  for w in pts.windows(2) {
+    let prev = u[u.len() - 1];
+    u.push(prev + dist(&w[0], &w[1]));
+  }
+  let total = u[u.len() - 1];
+  u.iter_mut().for_each(|t| *t /= total);
+  u
+}
+
+fn dedup(pts: &[Point]) -> Vec<Point> {
+  let mut res: Vec<Point> = Vec::with_capacity(pts.len());
+  for p in pts {
+    let is_new = match res.last() {
+      Some(last) => dist(last, p) > 0.0,
+      None => true,
+    };
+    if is_new {
+      res.push(Point::new(p.x, p.y));
+    }
+  }
+  res
+}
+
+// Направление от точки `i` вперёд, к первой точке не ближе `window`
+fn tangent_forward(pts: &[Point], i: usize, window: f64) -> Vec2 {
+  let j = (i + 1..pts.len())
+    .find(|&j| dist(&pts[j], &pts[i]) >= window)
+    .unwrap_or(pts.len() - 1);
+  normalize(sub(&pts[j], &pts[i]))
+}
+
+// Направление от точки `i` назад, к первой точке не ближе `window`
+fn tangent_backward(pts: &[Point], i: usize, window: f64) -> Vec2 {
+  let j = (0..i)
+    .rev()
+    .find(|&j| dist(&pts[j], &pts[i]) >= window)
+    .unwrap_or(0);
+  normalize(sub(&pts[j], &pts[i]))
+}
+
+// Индексы точек, где направление поворачивает больше, чем на `CORNER_ANGLE`.
+// Из нескольких соседних кандидатов остаётся точка с наибольшим поворотом.
+fn corners(pts: &[Point], window: f64) -> Vec<usize> {
+  let turn: Vec<f64> = (0..pts.len())
+    .map(|i| {
+      if i == 0 || i == pts.len() - 1 {
+        return 0.0;
+      }
+      let back = tangent_backward(pts, i, window);
+      let fwd = tangent_forward(pts, i, window);
+      // угол между входящим направлением (-back) и исходящим
+      (-dot(back, fwd)).clamp(-1.0, 1.0).acos()
+    })
+    .collect();
+
+  let mut res: Vec<usize> = Vec::new();
+  for i in 1..pts.len() - 1 {
+    if turn[i] <= CORNER_ANGLE {
+      continue;
+    }
+    match res.last_mut() {
+      Some(last) if dist(&pts[*last], &pts[i]) < window => {
+        if turn[i] > turn[*last] {
+          *last = i;
+        }
+      }
+      _ => res.push(i),
+    }
+  }
+  res
+}
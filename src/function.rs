@@ -0,0 +1,273 @@
+use crate::roots::{eval_poly, roots_in};
+use crate::SplineOpts;
+use crate::{calc_poly, calc_spline, Error, Extrapolation, Point, Points, PolySegment, Result};
+use std::cmp::Ordering;
+
+// Наибольшее число точек кубического продолжения с каждой стороны
+const MAX_EXTENSION_POINTS: f64 = 1000.0;
+
+///
+/// The curve calculated by [`calc_spline`] treated as a function `y(x)`.
+///
+/// It is calculated once and then gives values at any `x`, so use it to get many values of the curve.
+/// Beyond the first and the last points the curve is continued according to
+/// the [`Extrapolation`] from options.
+///
+/// # Example
+/// ```
+/// use cubic_spline::{Extrapolation, Points, SplineFunction, SplineOpts};
+///
+/// let pts = Points::from(&[(0.0, 0.0), (1.0, 1.0), (2.0, 2.0)]);
+/// let opts = SplineOpts::new().extrapolation(Extrapolation::Linear);
+///
+/// let f = SplineFunction::new(&pts, &opts).unwrap();
+/// assert!((f.value_at(0.5).unwrap() - 0.5).abs() < 1e-9);
+/// assert!((f.value_at(4.0).unwrap() - 4.0).abs() < 1e-9);
+///
+/// let f = pts.spline_function(&SplineOpts::new()).unwrap();
+/// assert!(f.value_at(4.0).is_none());
+/// ```
+///
+/// [`calc_spline`]: fn.calc_spline.html
+/// [`Extrapolation`]: enum.Extrapolation.html
+#[derive(Clone, Debug)]
+pub struct SplineFunction {
+  knots_x: Vec<f64>,
+  segments: Vec<PolySegment>,
+  extrapolation: Extrapolation,
+  num_of_segments: u32,
+}
+
+impl SplineFunction {
+  ///
+  /// Calculates the curve.
+  ///
+  /// Returns the same errors as [`calc_spline`] and [`Error::NotIncreasingX`]
+  /// if x-values are not strictly increasing.
+  ///
+  /// [`calc_spline`]: fn.calc_spline.html
+  /// [`Error::NotIncreasingX`]: enum.Error.html#variant.NotIncreasingX
+  pub fn new(points: &Points, opts: &SplineOpts) -> Result<Self> {
+    let segments = calc_poly(points, opts)?;
+    let increasing = |w: &[Point]| w[1].x.partial_cmp(&w[0].x) == Some(Ordering::Greater);
+    if !points.get_ref().windows(2).all(increasing) {
+      return Err(Error::NotIncreasingX);
+    }
+
+    Ok(SplineFunction {
+      knots_x: points.get_ref().iter().map(|p| p.x).collect(),
+      segments,
+      extrapolation: opts.get_extrapolation(),
+      num_of_segments: opts.get_num_of_segments(),
+    })
+  }
+
+  ///
+  /// Returns x-values of the first and the last points.
+  pub fn range(&self) -> (f64, f64) {
+    (self.knots_x[0], self.knots_x[self.knots_x.len() - 1])
+  }
+
+  ///
+  /// Returns the value of the curve at `x`
+  /// or `None` if `x` is outside of the range of points and [`Extrapolation::Error`] is used.
+  ///
+  /// [`Extrapolation::Error`]: enum.Extrapolation.html#variant.Error
+  pub fn value_at(&self, x: f64) -> Option<f64> {
+    let (from, to) = self.range();
+    if x >= from && x <= to {
+      let (k, t) = self.param_at(x);
+      return Some(eval_poly(&self.segments[k].y, t));
+    }
+
+    let (end, slope) = if x < from {
+      (from, self.end_slopes().0)
+    } else {
+      (to, self.end_slopes().1)
+    };
+    match self.extrapolation {
+      Extrapolation::Error => None,
+      Extrapolation::NaN => Some(f64::NAN),
+      _ if x.is_nan() => Some(f64::NAN),
+      Extrapolation::Constant => self.value_at(end),
+      Extrapolation::Linear => Some(self.value_at(end)? + slope * (x - end)),
+      Extrapolation::Cubic => {
+        let k = if x < from { 0 } else { self.segments.len() - 1 };
+        Some(self.cubic_at(k, x))
+      }
+    }
+  }
+
+  // Наклон dy/dx в начале и в конце кривой
+  fn end_slopes(&self) -> (f64, f64) {
+    let last = self.segments.len() - 1;
+    (
+      slope(&self.segments[0], 0.0),
+      slope(&self.segments[last], 1.0),
+    )
+  }
+
+  // Кубический многочлен от x с теми же значениями и наклонами на краях сегмента `k`, что и у кривой.
+  // В отличие от самого сегмента, x(t) которого может развернуться, он определён при любом x.
+  fn cubic_at(&self, k: usize, x: f64) -> f64 {
+    let seg = &self.segments[k];
+    let (x0, x1) = (self.knots_x[k], self.knots_x[k + 1]);
+    let (y0, y1) = (eval_poly(&seg.y, 0.0), eval_poly(&seg.y, 1.0));
+    let (s0, s1) = (slope(seg, 0.0), slope(seg, 1.0));
+
+    let h = x1 - x0;
+    let t = (x - x0) / h;
+    let (t2, t3) = (t * t, t * t * t);
+    (2.0 * t3 - 3.0 * t2 + 1.0) * y0
+      + (t3 - 2.0 * t2 + t) * h * s0
+      + (-2.0 * t3 + 3.0 * t2) * y1
+      + (t3 - t2) * h * s1
+  }
+
+  // Сегмент и параметр на нём, при котором кривая достигает `x` (x внутри диапазона точек)
+  fn param_at(&self, x: f64) -> (usize, f64) {
+    let knots_x = &self.knots_x;
+    let k = match knots_x.binary_search_by(|v| v.total_cmp(&x)) {
+      Ok(i) if i == knots_x.len() - 1 => return (i - 1, 1.0),
+      Ok(i) => return (i, 0.0),
+      Err(i) => i - 1,
+    };
+
+    let seg = &self.segments[k];
+    let c = [seg.x[0] - x, seg.x[1], seg.x[2], seg.x[3]];
+    let t = roots_in(&c, 0.0, 1.0).first().copied().unwrap_or_else(|| {
+      // из-за погрешностей корень может оказаться чуть за краем
+      if (x - knots_x[k]).abs() <= (x - knots_x[k + 1]).abs() {
+        0.0
+      } else {
+        1.0
+      }
+    });
+    (k, t)
+  }
+
+  // Точки продолжения кривой от ближайшего края до `target_x` без самой точки на краю.
+  // При `NaN` кривая не продолжается, прямое продолжение состоит из одной точки, кубическое идёт с той же плотностью по x,
+  // что и у крайнего сегмента, но не больше `MAX_EXTENSION_POINTS` точек
+  fn extension(&self, target_x: f64, before: bool) -> Result<Vec<Point>> {
+    let n = f64::from(self.num_of_segments);
+    let (from, to) = self.range();
+    let last = self.knots_x.len() - 1;
+    let (end_x, end_len) = if before {
+      (from, self.knots_x[1] - from)
+    } else {
+      (to, to - self.knots_x[last - 1])
+    };
+
+    // Доли пути от края к цели: (0, 1] с `count` шагами
+    let steps = |count: f64| {
+      let count = count.ceil().max(1.0) as usize;
+      (1..=count).map(move |i| i as f64 / count as f64)
+    };
+
+    let count = match self.extrapolation {
+      Extrapolation::Error => return Err(Error::OutOfRange),
+      Extrapolation::NaN => return Ok(Vec::new()),
+      Extrapolation::Constant | Extrapolation::Linear => 1.0,
+      Extrapolation::Cubic => ((target_x - end_x).abs() / end_len * n).min(MAX_EXTENSION_POINTS),
+    };
+    let mut res = steps(count)
+      .map(|s| {
+        let x = end_x + (target_x - end_x) * s;
+        self
+          .value_at(x)
+          .map(|y| Point::new(x, y))
+          .ok_or(Error::OutOfRange)
+      })
+      .collect::<Result<Vec<_>>>()?;
+
+    if before {
+      res.reverse();
+    }
+    Ok(res)
+  }
+}
+
+// Наклон dy/dx сегмента при параметре `t`, хорда там, где касательная вертикальна или нулевая
+fn slope(seg: &PolySegment, t: f64) -> f64 {
+  let d = |c: &[f64; 4]| c[1] + 2.0 * c[2] * t + 3.0 * c[3] * t * t;
+  let (dx, dy) = (d(&seg.x), d(&seg.y));
+  if dx != 0.0 {
+    dy / dx
+  } else {
+    let (a, b) = (seg.point_at(0.0), seg.point_at(1.0));
+    (b.y - a.y) / (b.x - a.x)
+  }
+}
+
+///
+/// Calculates the curve like [`calc_spline`] and continues it beyond the first and the last points
+/// to `x_from` and `x_to` according to the [`Extrapolation`] from options.
+/// With [`Extrapolation::NaN`] the curve is not continued. Constant and linear continuations
+/// are straight, so each of them is just one point at `x_from` or `x_to`. Cubic continuation has
+/// approximately the same density of points as the nearest piece of the curve,
+/// but not more than 1000 points on each side.
+///
+/// X-values of points should be strictly increasing. The curve is not continued to the left
+/// if `x_from` is not less than x of the first point, the same for `x_to` and the last point.
+///
+/// Returns the same errors as [`calc_spline`], [`Error::InvalidRange`] if `x_from` or `x_to`
+/// is not a finite number, [`Error::NotIncreasingX`] if x-values are not strictly increasing
+/// and [`Error::OutOfRange`] if the curve should be continued but [`Extrapolation::Error`] is used.
+///
+/// # Example
+/// ```
+/// use cubic_spline::{calc_spline_extended, Extrapolation, Points, SplineOpts};
+///
+/// let pts = Points::from(&[(0.0, 0.0), (1.0, 1.0), (2.0, 1.5), (3.0, 3.0)]);
+/// let opts = SplineOpts::new()
+///   .num_of_segments(4)
+///   .extrapolation(Extrapolation::Constant);
+///
+/// let curve = calc_spline_extended(&pts, &opts, -1.0, 5.0).unwrap();
+/// let curve = curve.get_ref();
+///
+/// assert_eq!(curve.len(), 1 + 13 + 1);
+/// assert!(curve[0].approx_eq(&(-1.0, 0.0).into()));
+/// assert!(curve[curve.len() - 1].approx_eq(&(5.0, 3.0).into()));
+/// ```
+///
+/// [`calc_spline`]: fn.calc_spline.html
+/// [`Extrapolation`]: enum.Extrapolation.html
+/// [`Extrapolation::Error`]: enum.Extrapolation.html#variant.Error
+/// [`Extrapolation::NaN`]: enum.Extrapolation.html#variant.NaN
+/// [`Error::InvalidRange`]: enum.Error.html#variant.InvalidRange
+/// [`Error::NotIncreasingX`]: enum.Error.html#variant.NotIncreasingX
+/// [`Error::OutOfRange`]: enum.Error.html#variant.OutOfRange
+pub fn calc_spline_extended(
+  points: &Points,
+  opts: &SplineOpts,
+  x_from: f64,
+  x_to: f64,
+) -> Result<Points> {
+  if !x_from.is_finite() || !x_to.is_finite() {
+    return Err(Error::InvalidRange);
+  }
+  let curve = SplineFunction::new(points, opts)?;
+  let (from, to) = curve.range();
+
+  let mut res = if x_from < from {
+    curve.extension(x_from, true)?
+  } else {
+    Vec::new()
+  };
+  res.extend(calc_spline(points, opts)?.into_inner());
+  if x_to > to {
+    res.extend(curve.extension(x_to, false)?);
+  }
+
+  Ok(Points::from(res))
+}
+
+///
+/// Calculates the curve as a function `y(x)`. The same as [`SplineFunction::new`].
+///
+/// [`SplineFunction::new`]: struct.SplineFunction.html#method.new
+pub fn spline_function(points: &Points, opts: &SplineOpts) -> Result<SplineFunction> {
+  SplineFunction::new(points, opts)
+}
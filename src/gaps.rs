@@ -0,0 +1,149 @@
+use crate::{calc_spline, Point, Points, Result, SplineOpts};
+
+///
+/// A list of options indicating where the points should be split into runs.
+///
+/// ```
+/// use cubic_spline::GapOpts;
+///
+/// let opts = GapOpts::new().max_gap(60.0);
+///
+/// assert_eq!(opts.get_max_gap(), Some(60.0));
+/// ```
+/// Options list:
+/// * `max_gap` -
+///   The biggest allowed distance by x between neighbouring points of one run.
+///   If not specified points are split only at gap markers.
+///
+/// Points with `NaN` (or any other non-finite) coordinate are always treated as gap markers.
+#[derive(Clone, Debug, Default)]
+pub struct GapOpts {
+  max_gap: Option<f64>,
+}
+
+impl GapOpts {
+  ///
+  /// Creates new one with defaults.
+  pub fn new() -> Self {
+    GapOpts::default()
+  }
+
+  ///
+  /// Sets max_gap.
+  pub fn max_gap(mut self, val: f64) -> Self {
+    self.max_gap = Some(val);
+    self
+  }
+
+  //
+  // Gets max_gap.
+  pub fn get_max_gap(&self) -> Option<f64> {
+    self.max_gap
+  }
+}
+
+///
+/// Splits points into runs without gaps.
+/// Gap markers (points with non-finite coordinates) are dropped,
+/// empty runs are skipped, a run may consist of a single point.
+///
+/// # Example
+/// ```
+/// use cubic_spline::{split_at_gaps, GapOpts, Points};
+///
+/// let pts = Points::from(&[
+///   (0.0, 1.0), (1.0, 2.0), (2.0, f64::NAN), (3.0, 1.0), (4.0, 3.0), (10.0, 2.0),
+/// ]);
+///
+/// let runs = split_at_gaps(&pts, &GapOpts::new().max_gap(2.0));
+///
+/// assert_eq!(runs.len(), 3);
+/// assert_eq!(runs[1].get_ref().len(), 2);
+/// assert_eq!(runs[2].get_ref().len(), 1);
+/// ```
+pub fn split_at_gaps(points: &Points, gap_opts: &GapOpts) -> Vec<Points> {
+  let mut runs = Vec::new();
+  let mut run: Vec<Point> = Vec::new();
+
+  for p in points.get_ref() {
+    if is_gap_marker(p) {
+      if !run.is_empty() {
+        runs.push(Points::from(std::mem::take(&mut run)));
+      }
+      continue;
+    }
+
+    let too_far = match (run.last(), gap_opts.get_max_gap()) {
+      (Some(last), Some(max_gap)) => (p.x - last.x).abs() > max_gap,
+      _ => false,
+    };
+    if too_far {
+      runs.push(Points::from(std::mem::take(&mut run)));
+    }
+    run.push(p.clone());
+  }
+
+  if !run.is_empty() {
+    runs.push(Points::from(run));
+  }
+  runs
+}
+
+// Точка с не конечной координатой отмечает разрыв
+fn is_gap_marker(p: &Point) -> bool {
+  !p.x.is_finite() || !p.y.is_finite()
+}
+
+///
+/// Splits points into runs like [`split_at_gaps`] and calculates the curve of each run independently,
+/// so the gaps stay visible as breaks between the returned curves.
+/// A run of a single point is returned as is.
+///
+/// Hidden points from options are used only at the start of the first run and at the end of the last one,
+/// and only if the points don't start (end) with a gap marker.
+///
+/// Returns the same errors as [`calc_spline`].
+///
+/// # Example
+/// ```
+/// use cubic_spline::{calc_spline_with_gaps, GapOpts, Points, SplineOpts};
+///
+/// let src = [0.0, 1.0, 1.0, 2.0, 2.0, f64::NAN, 3.0, 1.0, 4.0, 3.0];
+/// let pts = Points::try_from_flatten(&src).unwrap();
+/// let opts = SplineOpts::new().num_of_segments(8);
+///
+/// let runs = calc_spline_with_gaps(&pts, &opts, &GapOpts::default()).unwrap();
+///
+/// assert_eq!(runs.len(), 2);
+/// assert_eq!(runs[0].get_ref().len(), 9);
+/// assert!(runs.iter().flat_map(|r| r.get_ref()).all(|p| !p.y.is_nan()));
+/// ```
+///
+/// [`split_at_gaps`]: fn.split_at_gaps.html
+/// [`calc_spline`]: fn.calc_spline.html
+pub fn calc_spline_with_gaps(
+  points: &Points,
+  opts: &SplineOpts,
+  gap_opts: &GapOpts,
+) -> Result<Vec<Points>> {
+  let runs = split_at_gaps(points, gap_opts);
+  let last = runs.len().saturating_sub(1);
+  let pts = points.get_ref();
+  let gap_at_start = matches!(pts.first(), Some(p) if is_gap_marker(p));
+  let gap_at_end = matches!(pts.last(), Some(p) if is_gap_marker(p));
+
+  runs
+    .into_iter()
+    .enumerate()
+    .map(|(i, run)| {
+      if run.get_ref().len() < 2 {
+        return Ok(run);
+      }
+      // скрытые точки относятся к краям всего ряда, а не к краям разрывов
+      let run_opts = opts
+        .clone()
+        .without_hidden_points(i > 0 || gap_at_start, i < last || gap_at_end);
+      calc_spline(&run, &run_opts)
+    })
+    .collect()
+}
@@ -0,0 +1,386 @@
+use crate::roots::{derivative, roots_in};
+use crate::{BezierSegment, Point, PolySegment};
+
+const MAX_DEPTH: usize = 48;
+const FLATNESS: f64 = 1e-6;
+const NEWTON_ITERATIONS: usize = 16;
+
+///
+/// A point where two curves (or a curve and a line) cross.
+///
+/// For intersections with a line `segment_b` is always `0`
+/// and `t_b` is the parameter on the line: `0.0` at its first point and `1.0` at the second.
+#[derive(Clone, Debug)]
+pub struct Intersection {
+  ///
+  /// Coordinates of the intersection.
+  pub point: Point,
+
+  ///
+  /// Index of the segment on the first curve.
+  pub segment_a: usize,
+
+  ///
+  /// Parameter on the segment of the first curve.
+  pub t_a: f64,
+
+  ///
+  /// Index of the segment on the second curve.
+  pub segment_b: usize,
+
+  ///
+  /// Parameter on the segment of the second curve.
+  pub t_b: f64,
+}
+
+///
+/// Finds all intersections of the curve with the infinite line passing through `from` and `to`.
+/// Intersections are sorted along the curve.
+///
+/// # Example
+/// ```
+/// use cubic_spline::{intersect_line, Points, SplineOpts};
+///
+/// let pts = Points::from(&[(0.0, 0.0), (10.0, 10.0), (20.0, 0.0), (30.0, 10.0)]);
+/// let segments = pts.calc_bezier(&SplineOpts::default()).unwrap();
+///
+/// // horizontal threshold at y = 5
+/// let crossings = intersect_line(&segments, &(0.0, 5.0).into(), &(1.0, 5.0).into());
+///
+/// assert_eq!(crossings.len(), 3);
+/// assert!(crossings[1].point.approx_eq(&(15.0, 5.0).into()));
+/// ```
+pub fn intersect_line(segments: &[BezierSegment], from: &Point, to: &Point) -> Vec<Intersection> {
+  let (dx, dy) = (to.x - from.x, to.y - from.y);
+  let len_sq = dx * dx + dy * dy;
+  if len_sq == 0.0 {
+    return Vec::new();
+  }
+
+  let mut res = Vec::new();
+
+  for (i, seg) in segments.iter().enumerate() {
+    // Расстояние со знаком до прямой: (P(t) - from) x (to - from) = 0
+    let poly = PolySegment::from(seg);
+    let mut c = [0.0; 4];
+    for (k, ck) in c.iter_mut().enumerate() {
+      *ck = poly.x[k] * dy - poly.y[k] * dx;
+    }
+    c[0] -= from.x * dy - from.y * dx;
+
+    for t in roots_in(&c, 0.0, 1.0) {
+      let point = poly.point_at(t);
+      let t_b = ((point.x - from.x) * dx + (point.y - from.y) * dy) / len_sq;
+      res.push(Intersection {
+        point,
+        segment_a: i,
+        t_a: t,
+        segment_b: 0,
+        t_b,
+      });
+    }
+  }
+
+  // Точка в конце сегмента совпадает с точкой в начале следующего
+  dedup(res, FLATNESS * scale(segments.iter()))
+}
+
+///
+/// The same as [`intersect_line`] but only intersections lying between `from` and `to` are returned.
+///
+/// [`intersect_line`]: fn.intersect_line.html
+pub fn intersect_line_segment(
+  segments: &[BezierSegment],
+  from: &Point,
+  to: &Point,
+) -> Vec<Intersection> {
+  let mut res = intersect_line(segments, from, to);
+  res.retain(|r| r.t_b >= 0.0 && r.t_b <= 1.0);
+  res
+}
+
+///
+/// Finds all intersections of two curves.
+/// Intersections are sorted along the first curve.
+///
+/// Segments are subdivided while their control polygons overlap,
+/// then the result is refined with Newton's method on the exact segments.
+/// Parts of curves that coincide are not reported.
+///
+/// # Example
+/// ```
+/// use cubic_spline::{intersect, Points, SplineOpts};
+///
+/// let opts = SplineOpts::default();
+/// let revenue = Points::from(&[(0.0, 0.0), (5.0, 6.0), (10.0, 10.0)]);
+/// let cost = Points::from(&[(0.0, 4.0), (5.0, 5.0), (10.0, 6.0)]);
+///
+/// let crossings = intersect(
+///   &revenue.calc_bezier(&opts).unwrap(),
+///   &cost.calc_bezier(&opts).unwrap(),
+/// );
+///
+/// assert_eq!(crossings.len(), 1);
+/// assert_eq!(crossings[0].segment_a, 0);
+/// assert!(crossings[0].point.x > 0.0 && crossings[0].point.x < 5.0);
+/// ```
+pub fn intersect(a: &[BezierSegment], b: &[BezierSegment]) -> Vec<Intersection> {
+  let tol = FLATNESS * scale(a.iter().chain(b.iter()));
+  let mut res = Vec::new();
+
+  for (i, sa) in a.iter().enumerate() {
+    for (j, sb) in b.iter().enumerate() {
+      let pa = Piece::new(i, sa);
+      let pb = Piece::new(j, sb);
+      intersect_pieces(&pa, &pb, tol, 0, &mut res);
+    }
+  }
+
+  dedup(res, tol)
+}
+
+///
+/// Finds points where the curve crosses itself.
+/// `segment_a`/`t_a` always point to the earlier place on the curve.
+/// If the curve is closed (it ends where it starts) the joint of its ends is not a crossing.
+///
+/// # Example
+/// ```
+/// use cubic_spline::{self_intersections, Points, SplineOpts};
+///
+/// let pts = Points::from(&[(0.0, 0.0), (10.0, 10.0), (10.0, 0.0), (0.0, 10.0)]);
+/// let loops = self_intersections(&pts.calc_bezier(&SplineOpts::default()).unwrap());
+///
+/// assert_eq!(loops.len(), 1);
+/// assert!(loops[0].segment_a < loops[0].segment_b);
+/// ```
+pub fn self_intersections(segments: &[BezierSegment]) -> Vec<Intersection> {
+  let tol = FLATNESS * scale(segments.iter());
+
+  // Кривая, монотонная по x, не может пересечь сама себя,
+  // поэтому сегменты разбиваются в экстремумах по x
+  let mut pieces = Vec::new();
+  for (i, seg) in segments.iter().enumerate() {
+    let poly = PolySegment::from(seg);
+    let dx = derivative(&poly.x);
+    let mut rest = Piece::new(i, seg);
+    for t in roots_in(&dx, 0.0, 1.0) {
+      if t <= 0.0 || t >= 1.0 {
+        continue;
+      }
+      let local = (t - rest.t0) / (rest.t1 - rest.t0);
+      let (left, right) = rest.split(local);
+      pieces.push(left);
+      rest = right;
+    }
+    pieces.push(rest);
+  }
+
+  let mut res = Vec::new();
+  for (k, pa) in pieces.iter().enumerate() {
+    for pb in pieces.iter().skip(k + 1) {
+      intersect_pieces(pa, pb, tol, 0, &mut res);
+    }
+  }
+
+  // Соседние куски касаются друг друга в общей точке, это не пересечение.
+  // У замкнутой кривой соседями являются и первый с последним сегментом.
+  let len = segments.len() as f64;
+  let closed = match (segments.first(), segments.last()) {
+    (Some(first), Some(last)) => segments.len() > 1 && first.p0.approx_eq(&last.p3),
+    _ => false,
+  };
+  res.retain(|r| {
+    let ga = r.segment_a as f64 + r.t_a;
+    let gb = r.segment_b as f64 + r.t_b;
+    let d = (gb - ga).abs();
+    let d = if closed { d.min(len - d) } else { d };
+    d > 1e-6
+  });
+
+  dedup(res, tol)
+}
+
+// Часть сегмента с индексом `index` для параметров от `t0` до `t1`
+struct Piece<'a> {
+  index: usize,
+  origin: &'a BezierSegment,
+  seg: BezierSegment,
+  t0: f64,
+  t1: f64,
+}
+
+impl<'a> Piece<'a> {
+  fn new(index: usize, origin: &'a BezierSegment) -> Self {
+    Piece {
+      index,
+      origin,
+      seg: origin.clone(),
+      t0: 0.0,
+      t1: 1.0,
+    }
+  }
+
+  fn split(&self, t: f64) -> (Piece<'a>, Piece<'a>) {
+    let (left, right) = self.seg.split(t);
+    let mid = self.t0 + (self.t1 - self.t0) * t;
+    (
+      Piece {
+        seg: left,
+        t1: mid,
+        ..*self
+      },
+      Piece {
+        seg: right,
+        t0: mid,
+        ..*self
+      },
+    )
+  }
+
+  fn is_flat(&self, tol: f64) -> bool {
+    let s = &self.seg;
+    dist_to_line(&s.c1, &s.p0, &s.p3) <= tol && dist_to_line(&s.c2, &s.p0, &s.p3) <= tol
+  }
+}
+
+fn dist_to_line(p: &Point, a: &Point, b: &Point) -> f64 {
+  let (dx, dy) = (b.x - a.x, b.y - a.y);
+  let len = dx.hypot(dy);
+  if len == 0.0 {
+    return (p.x - a.x).hypot(p.y - a.y);
+  }
+  ((p.x - a.x) * dy - (p.y - a.y) * dx).abs() / len
+}
+
+fn scale<'a, I: Iterator<Item = &'a BezierSegment>>(segments: I) -> f64 {
+  let (x0, y0, x1, y1) = segments.map(BezierSegment::hull_bounds).fold(
+    (
+      f64::INFINITY,
+      f64::INFINITY,
+      f64::NEG_INFINITY,
+      f64::NEG_INFINITY,
+    ),
+    |a, b| (a.0.min(b.0), a.1.min(b.1), a.2.max(b.2), a.3.max(b.3)),
+  );
+  (x1 - x0).max(y1 - y0).max(1.0)
+}
+
+fn overlap(a: &BezierSegment, b: &BezierSegment, tol: f64) -> bool {
+  let (ax0, ay0, ax1, ay1) = a.hull_bounds();
+  let (bx0, by0, bx1, by1) = b.hull_bounds();
+  ax0 <= bx1 + tol && bx0 <= ax1 + tol && ay0 <= by1 + tol && by0 <= ay1 + tol
+}
+
+fn intersect_pieces(a: &Piece, b: &Piece, tol: f64, depth: usize, res: &mut Vec<Intersection>) {
+  if !overlap(&a.seg, &b.seg, tol) {
+    return;
+  }
+
+  let a_flat = a.is_flat(tol);
+  let b_flat = b.is_flat(tol);
+
+  if (a_flat && b_flat) || depth >= MAX_DEPTH {
+    if let Some((s, u)) = chords_intersection(&a.seg, &b.seg) {
+      let ta = a.t0 + (a.t1 - a.t0) * s;
+      let tb = b.t0 + (b.t1 - b.t0) * u;
+      let (ta, tb) = refine(a.origin, b.origin, ta, tb);
+      res.push(Intersection {
+        point: a.origin.point_at(ta),
+        segment_a: a.index,
+        t_a: ta,
+        segment_b: b.index,
+        t_b: tb,
+      });
+    }
+    return;
+  }
+
+  // Делится только тот кусок, который ещё не плоский
+  match (a_flat, b_flat) {
+    (true, false) => {
+      let (b1, b2) = b.split(0.5);
+      intersect_pieces(a, &b1, tol, depth + 1, res);
+      intersect_pieces(a, &b2, tol, depth + 1, res);
+    }
+    (false, true) => {
+      let (a1, a2) = a.split(0.5);
+      intersect_pieces(&a1, b, tol, depth + 1, res);
+      intersect_pieces(&a2, b, tol, depth + 1, res);
+    }
+    _ => {
+      let (a1, a2) = a.split(0.5);
+      let (b1, b2) = b.split(0.5);
+      intersect_pieces(&a1, &b1, tol, depth + 1, res);
+      intersect_pieces(&a1, &b2, tol, depth + 1, res);
+      intersect_pieces(&a2, &b1, tol, depth + 1, res);
+      intersect_pieces(&a2, &b2, tol, depth + 1, res);
+    }
+  }
+}
+
+// Пересечение хорд `p0 -> p3` двух кусков. Параллельные хорды не пересекаются.
+fn chords_intersection(a: &BezierSegment, b: &BezierSegment) -> Option<(f64, f64)> {
+  let (rx, ry) = (a.p3.x - a.p0.x, a.p3.y - a.p0.y);
+  let (sx, sy) = (b.p3.x - b.p0.x, b.p3.y - b.p0.y);
+  let denom = rx * sy - ry * sx;
+  if denom == 0.0 {
+    return None;
+  }
+
+  let (qx, qy) = (b.p0.x - a.p0.x, b.p0.y - a.p0.y);
+  let s = (qx * sy - qy * sx) / denom;
+  let u = (qx * ry - qy * rx) / denom;
+
+  let range = -1e-9..=1.0 + 1e-9;
+  if range.contains(&s) && range.contains(&u) {
+    Some((s.clamp(0.0, 1.0), u.clamp(0.0, 1.0)))
+  } else {
+    None
+  }
+}
+
+// Уточнение методом Ньютона для A(s) - B(u) = 0
+fn refine(a: &BezierSegment, b: &BezierSegment, mut s: f64, mut u: f64) -> (f64, f64) {
+  for _ in 0..NEWTON_ITERATIONS {
+    let pa = a.point_at(s);
+    let pb = b.point_at(u);
+    let (fx, fy) = (pa.x - pb.x, pa.y - pb.y);
+    if fx == 0.0 && fy == 0.0 {
+      break;
+    }
+
+    let da = a.derivative_at(s);
+    let db = b.derivative_at(u);
+    let det = -da.x * db.y + da.y * db.x;
+    if det == 0.0 {
+      break;
+    }
+
+    let ds = (-fx * db.y + fy * db.x) / det;
+    let du = (da.x * fy - da.y * fx) / det;
+    let next_s = (s - ds).clamp(0.0, 1.0);
+    let next_u = (u - du).clamp(0.0, 1.0);
+    if next_s == s && next_u == u {
+      break;
+    }
+    s = next_s;
+    u = next_u;
+  }
+  (s, u)
+}
+
+fn dedup(mut res: Vec<Intersection>, tol: f64) -> Vec<Intersection> {
+  res.sort_by(|a, b| (a.segment_a as f64 + a.t_a).total_cmp(&(b.segment_a as f64 + b.t_a)));
+
+  let mut out: Vec<Intersection> = Vec::with_capacity(res.len());
+  for r in res {
+    let is_dup = out
+      .iter()
+      .any(|o| o.point.approx_eq_with_precision(&r.point, tol * 10.0));
+    if !is_dup {
+      out.push(r);
+    }
+  }
+  out
+}
@@ -69,23 +69,88 @@
 //!
 //! ```
 //!
+//! ## Serde
+//!
+//! Enable the `serde` feature to (de)serialize [`Point`], [`Points`] and [`SplineOpts`].
+//! A point can be read from `[x, y]` or `{"x": .., "y": .., "tension": ..}`,
+//! points - from a list of such values or from a flatten array `[x, y, x, y, ...]`.
+//! Deserialization of [`Points`] fails the same way as [`try_from`] does.
+//!
+//! [`Point`]: struct.Point.html
+//! [`Points`]: struct.Points.html
+//! [`SplineOpts`]: struct.SplineOpts.html
+//! [`try_from`]: trait.TryFrom.html#tymethod.try_from
+//!
 
 #[cfg(test)]
 mod tests;
 
+mod analysis;
+mod area;
+mod bbox;
+mod bezier;
 mod calc;
+mod clip;
 mod err;
+mod fit;
+mod fit_bezier;
+mod function;
+mod gaps;
+mod intersect;
+mod linalg;
+mod offset;
 mod opts;
 mod points;
 mod points_iter;
+mod poly;
+mod project;
+mod resample;
+mod roots;
+mod simplify;
+mod smoothing;
+mod spline;
+mod split;
+mod stream;
+mod svg;
 mod tfti;
+mod transform;
+mod viewport;
 
+pub use analysis::{extrema_x, extrema_y, inflections, CurvePoint, Extremum, ExtremumKind};
+pub use area::{integrate, signed_area};
+pub use bbox::{bounding_box, BoundingBox, Extent};
+pub use bezier::{calc_bezier, BezierSegment};
 pub use calc::calc_spline;
+pub use clip::{clip_curve, clip_spline, Rect};
 
 pub use err::{Error, Result};
-pub use opts::{SplineOpts, DEFAULT_SEGMENTS, DEFAULT_TENSION};
+pub use fit::{fit_spline, Fit, FitOpts, DEFAULT_FIT_KNOTS};
+pub use fit_bezier::{fit_bezier, fit_knots};
+pub use function::{calc_spline_extended, spline_function, SplineFunction};
+pub use gaps::{calc_spline_with_gaps, split_at_gaps, GapOpts};
+pub use intersect::{
+  intersect, intersect_line, intersect_line_segment, self_intersections, Intersection,
+};
+pub use offset::{
+  offset_curve, stroke_outline, LineCap, LineJoin, StrokeOpts, DEFAULT_STROKE_TOLERANCE,
+};
+pub use opts::{Extrapolation, SplineOpts, DEFAULT_SEGMENTS, DEFAULT_TENSION};
 pub use points::{Point, Points, DEFAULT_APPROX_EQ_PRECISION};
+pub use poly::{calc_poly, pack_poly, PolySegment};
+pub use project::{closest_point, Projection};
+pub use resample::{resample, Grid, ResampleOpts, SplineMode};
+pub use simplify::{simplify_rdp, simplify_vw};
+pub use smoothing::{calc_smoothing_spline, SmoothingOpts, SmoothingSpline};
+pub use spline::Spline;
+pub use split::{split_curve, split_curve_at_x, sub_curve, sub_curve_x};
+pub use stream::SplineStream;
+pub use svg::{parse_svg_path, DEFAULT_SVG_PRECISION};
 pub use tfti::{TryFrom, TryInto};
+pub use transform::Transform;
+pub use viewport::{Scale, Viewport};
+
+#[cfg(feature = "serde")]
+mod serde_impl;
 
 #[cfg(target_arch = "wasm32")]
 mod wasm;
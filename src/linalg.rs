@@ -0,0 +1,86 @@
+// Симметричная положительно определённая ленточная матрица.
+// Хранится только нижняя часть ленты: `data[i * (bw + 1) + (i - j)] = A[i][j]` для `i - bw <= j <= i`.
+pub(crate) struct BandMatrix {
+  n: usize,
+  bw: usize,
+  data: Vec<f64>,
+}
+
+impl BandMatrix {
+  pub(crate) fn new(n: usize, bw: usize) -> Self {
+    BandMatrix {
+      n,
+      bw,
+      data: vec![0.0; n * (bw + 1)],
+    }
+  }
+
+  fn idx(&self, i: usize, j: usize) -> usize {
+    let (i, j) = if i >= j { (i, j) } else { (j, i) };
+    debug_assert!(i - j <= self.bw, "element is outside of the band");
+    i * (self.bw + 1) + (i - j)
+  }
+
+  // Прибавляет `v` к `A[i][j]` (и симметрично к `A[j][i]`)
+  pub(crate) fn add(&mut self, i: usize, j: usize, v: f64) {
+    let idx = self.idx(i, j);
+    self.data[idx] += v;
+  }
+
+  // Решает `A x = b` разложением Холецкого.
+  // Возвращает `None`, если матрица вырождена или не положительно определена.
+  pub(crate) fn solve(mut self, mut b: Vec<f64>) -> Option<Vec<f64>> {
+    let (n, bw) = (self.n, self.bw);
+    let scale = (0..n)
+      .map(|i| self.data[self.idx(i, i)].abs())
+      .fold(0.0, f64::max);
+    let eps = scale * 1e-13;
+
+    // A = L Lᵀ, L записывается на место A
+    for j in 0..n {
+      let from = j.saturating_sub(bw);
+      let mut d = self.data[self.idx(j, j)];
+      for k in from..j {
+        let l = self.data[self.idx(j, k)];
+        d -= l * l;
+      }
+      if d.is_nan() || d <= eps {
+        return None;
+      }
+      let d = d.sqrt();
+      let jj = self.idx(j, j);
+      self.data[jj] = d;
+
+      for i in j + 1..n.min(j + bw + 1) {
+        let mut s = self.data[self.idx(i, j)];
+        for k in i.saturating_sub(bw)..j {
+          s -= self.data[self.idx(i, k)] * self.data[self.idx(j, k)];
+        }
+        let ij = self.idx(i, j);
+        self.data[ij] = s / d;
+      }
+    }
+
+    // L y = b
+    for i in 0..n {
+      let from = i.saturating_sub(bw);
+      let s: f64 = (from..i)
+        .zip(&b[from..i])
+        .map(|(k, bk)| self.data[self.idx(i, k)] * bk)
+        .sum();
+      b[i] = (b[i] - s) / self.data[self.idx(i, i)];
+    }
+
+    // Lᵀ x = y
+    for i in (0..n).rev() {
+      let to = n.min(i + bw + 1);
+      let s: f64 = (i + 1..to)
+        .zip(&b[i + 1..to])
+        .map(|(k, bk)| self.data[self.idx(k, i)] * bk)
+        .sum();
+      b[i] = (b[i] - s) / self.data[self.idx(i, i)];
+    }
+
+    Some(b)
+  }
+}
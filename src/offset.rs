@@ -0,0 +1,416 @@
+use crate::{BezierSegment, Point, Points};
+use std::f64::consts::PI;
+
+///
+/// Default tolerance of offset curves and stroke outlines.
+/// Will be used if not specified in [`StrokeOpts`].
+///
+/// [`StrokeOpts`]: struct.StrokeOpts.html
+pub const DEFAULT_STROKE_TOLERANCE: f64 = 0.1;
+
+const MIN_DEPTH: usize = 2;
+const MAX_DEPTH: usize = 16;
+// Касательные, отличающиеся меньше, чем на этот угол, не требуют соединения
+const JOIN_ANGLE_EPS: f64 = 1e-6;
+
+///
+/// How two pieces of the stroke are connected where the curve has a corner.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LineJoin {
+  ///
+  /// Sharp corner. If the ratio of the miter length to the half of the width
+  /// is greater than the passed limit the corner is cut as with `Bevel`.
+  Miter(f64),
+
+  ///
+  /// Rounded corner.
+  Round,
+
+  ///
+  /// Cut corner.
+  Bevel,
+}
+
+///
+/// How the ends of the stroke look.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LineCap {
+  ///
+  /// The stroke ends exactly at the end of the curve.
+  Butt,
+
+  ///
+  /// The stroke ends with a half circle.
+  Round,
+
+  ///
+  /// The stroke is extended beyond the end of the curve by a half of the width.
+  Square,
+}
+
+///
+/// A list of options indicating how offset curves and stroke outlines should be calculated.
+///
+/// ```
+/// use cubic_spline::{LineCap, LineJoin, StrokeOpts};
+///
+/// let opts = StrokeOpts::new()
+///   .width(4.0)
+///   .join(LineJoin::Miter(4.0))
+///   .cap(LineCap::Round)
+///   .tolerance(0.05);
+///
+/// assert_eq!(opts.get_join(), LineJoin::Miter(4.0));
+/// ```
+/// Options list:
+/// * `width` - Width of the stroke. Default is `1.0`.
+///
+/// * `join` - How corners are connected. Default is [`LineJoin::Round`].
+///
+/// * `cap` - How the ends look. Default is [`LineCap::Butt`].
+///
+/// * `tolerance` -
+///   The maximum distance between the calculated polyline and the exact offset curve.
+///   If not specified [`DEFAULT_STROKE_TOLERANCE`] will be used.
+///
+/// [`LineJoin::Round`]: enum.LineJoin.html#variant.Round
+/// [`LineCap::Butt`]: enum.LineCap.html#variant.Butt
+/// [`DEFAULT_STROKE_TOLERANCE`]: constant.DEFAULT_STROKE_TOLERANCE.html
+#[derive(Clone, Debug)]
+pub struct StrokeOpts {
+  width: f64,
+  join: LineJoin,
+  cap: LineCap,
+  tolerance: f64,
+}
+
+impl StrokeOpts {
+  ///
+  /// Creates new one with defaults.
+  pub fn new() -> Self {
+    StrokeOpts::default()
+  }
+
+  ///
+  /// Sets width.
+  pub fn width(mut self, val: f64) -> Self {
+    self.width = val;
+    self
+  }
+
+  ///
+  /// Sets join.
+  pub fn join(mut self, val: LineJoin) -> Self {
+    self.join = val;
+    self
+  }
+
+  ///
+  /// Sets cap.
+  pub fn cap(mut self, val: LineCap) -> Self {
+    self.cap = val;
+    self
+  }
+
+  ///
+  /// Sets tolerance.
+  pub fn tolerance(mut self, val: f64) -> Self {
+    self.tolerance = val;
+    self
+  }
+
+  //
+  // Gets width.
+  pub fn get_width(&self) -> f64 {
+    self.width
+  }
+
+  //
+  // Gets join.
+  pub fn get_join(&self) -> LineJoin {
+    self.join
+  }
+
+  //
+  // Gets cap.
+  pub fn get_cap(&self) -> LineCap {
+    self.cap
+  }
+
+  //
+  // Gets tolerance.
+  pub fn get_tolerance(&self) -> f64 {
+    self.tolerance
+  }
+}
+
+impl Default for StrokeOpts {
+  fn default() -> Self {
+    StrokeOpts {
+      width: 1.0,
+      join: LineJoin::Round,
+      cap: LineCap::Butt,
+      tolerance: DEFAULT_STROKE_TOLERANCE,
+    }
+  }
+}
+
+// Единичная касательная. Если производная нулевая (например при нулевом натяжении),
+// направление берётся по соседней точке кривой.
+fn unit_tangent(seg: &BezierSegment, t: f64) -> (f64, f64) {
+  let d = seg.derivative_at(t);
+  let len = d.x.hypot(d.y);
+  if len > 1e-12 {
+    return (d.x / len, d.y / len);
+  }
+
+  let h = 1e-4;
+  let (a, b) = if t + h <= 1.0 {
+    (seg.point_at(t), seg.point_at(t + h))
+  } else {
+    (seg.point_at(t - h), seg.point_at(t))
+  };
+  let (dx, dy) = (b.x - a.x, b.y - a.y);
+  let len = dx.hypot(dy);
+  if len > 0.0 {
+    (dx / len, dy / len)
+  } else {
+    let (dx, dy) = (seg.p3.x - seg.p0.x, seg.p3.y - seg.p0.y);
+    let len = dx.hypot(dy).max(f64::MIN_POSITIVE);
+    (dx / len, dy / len)
+  }
+}
+
+// Точка, сдвинутая на `distance` влево от направления движения
+fn shift(p: &Point, (tx, ty): (f64, f64), distance: f64) -> Point {
+  Point::new(p.x - ty * distance, p.y + tx * distance)
+}
+
+fn offset_at(seg: &BezierSegment, t: f64, distance: f64) -> Point {
+  shift(&seg.point_at(t), unit_tangent(seg, t), distance)
+}
+
+fn dist_to_chord(p: &Point, a: &Point, b: &Point) -> f64 {
+  let (dx, dy) = (b.x - a.x, b.y - a.y);
+  let len = dx.hypot(dy);
+  if len == 0.0 {
+    return (p.x - a.x).hypot(p.y - a.y);
+  }
+  ((p.x - a.x) * dy - (p.y - a.y) * dx).abs() / len
+}
+
+// Разбиение смещённого сегмента на отрезки, отклоняющиеся от точной кривой не больше, чем на `tolerance`
+struct Subdivision<'a> {
+  seg: &'a BezierSegment,
+  distance: f64,
+  tolerance: f64,
+}
+
+impl Subdivision<'_> {
+  // Добавляет в `out` точки смещённой кривой на участке `(t0, t1)` от `q0` (не включая) до `q1`
+  fn run(&self, (t0, t1): (f64, f64), q0: &Point, q1: Point, depth: usize, out: &mut Vec<Point>) {
+    let tm = 0.5 * (t0 + t1);
+    let qm = offset_at(self.seg, tm, self.distance);
+
+    let is_flat = dist_to_chord(&qm, q0, &q1) <= self.tolerance;
+    if depth >= MAX_DEPTH || (depth >= MIN_DEPTH && is_flat) {
+      out.push(q1);
+      return;
+    }
+
+    self.run((t0, tm), q0, qm.clone(), depth + 1, out);
+    self.run((tm, t1), &qm, q1, depth + 1, out);
+  }
+}
+
+// Дуга окружности вокруг `center` от угла `from` на угол `sweep`, без начальной точки
+fn arc(center: &Point, radius: f64, from: f64, sweep: f64, tolerance: f64, out: &mut Vec<Point>) {
+  let max_step = if tolerance < radius {
+    2.0 * (1.0 - tolerance / radius).acos()
+  } else {
+    PI / 2.0
+  };
+  let steps = (sweep.abs() / max_step).ceil().max(1.0) as usize;
+
+  for i in 1..=steps {
+    let a = from + sweep * i as f64 / steps as f64;
+    out.push(Point::new(
+      center.x + radius * a.cos(),
+      center.y + radius * a.sin(),
+    ));
+  }
+}
+
+fn join(
+  center: &Point,
+  a: (f64, f64),
+  b: (f64, f64),
+  distance: f64,
+  opts: &StrokeOpts,
+  out: &mut Vec<Point>,
+) {
+  let cross = a.0 * b.1 - a.1 * b.0;
+  let dot = a.0 * b.0 + a.1 * b.1;
+  let angle = cross.atan2(dot);
+  let end = shift(center, b, distance);
+
+  // Угла нет, конец предыдущего сегмента уже в `out`
+  if angle.abs() < JOIN_ANGLE_EPS {
+    return;
+  }
+
+  // Внутренняя сторона угла
+  if angle * distance > 0.0 {
+    out.push(end);
+    return;
+  }
+
+  match opts.join {
+    LineJoin::Bevel => out.push(end),
+    LineJoin::Round => {
+      let start = shift(center, a, distance);
+      let from = (start.y - center.y).atan2(start.x - center.x);
+      arc(center, distance.abs(), from, angle, opts.tolerance, out);
+    }
+    LineJoin::Miter(limit) => {
+      // Длина острия относительно половины ширины: 1 / cos(angle / 2)
+      let ratio = 1.0 / (angle / 2.0).cos();
+      if ratio <= limit {
+        let (nx, ny) = (-(a.1 + b.1), a.0 + b.0);
+        let scale = distance / (1.0 + dot);
+        out.push(Point::new(center.x + nx * scale, center.y + ny * scale));
+      }
+      out.push(end);
+    }
+  }
+}
+
+fn offset_polyline(segments: &[BezierSegment], distance: f64, opts: &StrokeOpts) -> Vec<Point> {
+  let mut out = Vec::new();
+  let tolerance = opts.tolerance.max(f64::EPSILON);
+
+  for (i, seg) in segments.iter().enumerate() {
+    let start = offset_at(seg, 0.0, distance);
+    if i == 0 {
+      out.push(start.clone());
+    } else {
+      let prev = &segments[i - 1];
+      join(
+        &seg.p0,
+        unit_tangent(prev, 1.0),
+        unit_tangent(seg, 0.0),
+        distance,
+        opts,
+        &mut out,
+      );
+    }
+
+    let end = offset_at(seg, 1.0, distance);
+    let subdivision = Subdivision {
+      seg,
+      distance,
+      tolerance,
+    };
+    subdivision.run((0.0, 1.0), &start, end, 0, &mut out);
+  }
+
+  out
+}
+
+///
+/// Returns the curve shifted by the signed distance along its normal
+/// (to the left of the direction of the curve in the coordinate system with y-axis up)
+/// as a polyline. Corners of the curve are connected according to `join` from options.
+/// The polyline differs from the exact offset curve not more than by `tolerance`.
+///
+/// Where the curve bends sharper than the offset distance (the radius of curvature is less than
+/// `distance`) the offset curve makes a loop on the concave side. Such loops are not trimmed.
+///
+/// # Example
+/// ```
+/// use cubic_spline::{offset_curve, Points, SplineOpts, StrokeOpts};
+///
+/// let pts = Points::from(&[(0.0, 0.0), (10.0, 0.0), (20.0, 0.0)]);
+/// let segments = pts.calc_bezier(&SplineOpts::default()).unwrap();
+///
+/// let upper = offset_curve(&segments, 2.0, &StrokeOpts::default());
+///
+/// assert!(upper.get_ref().iter().all(|p| (p.y - 2.0).abs() < 1e-9));
+/// assert!(upper.get_ref()[0].approx_eq(&(0.0, 2.0).into()));
+/// ```
+pub fn offset_curve(segments: &[BezierSegment], distance: f64, opts: &StrokeOpts) -> Points {
+  Points::from(offset_polyline(segments, distance, opts))
+}
+
+fn cap(
+  center: &Point,
+  (tx, ty): (f64, f64),
+  half_width: f64,
+  opts: &StrokeOpts,
+  out: &mut Vec<Point>,
+) {
+  // Переход с левой стороны на правую вокруг конца кривой, направленного по `(tx, ty)`
+  match opts.cap {
+    LineCap::Butt => {}
+    LineCap::Square => {
+      let ext = Point::new(center.x + tx * half_width, center.y + ty * half_width);
+      out.push(shift(&ext, (tx, ty), half_width));
+      out.push(shift(&ext, (tx, ty), -half_width));
+    }
+    LineCap::Round => {
+      let from = tx.atan2(-ty);
+      arc(center, half_width, from, -PI, opts.tolerance, out);
+      out.pop();
+    }
+  }
+}
+
+///
+/// Returns outline of the stroke of the curve as a closed polygon
+/// (the last point should be connected to the first one).
+///
+/// It goes along the left side of the curve, around the end,
+/// back along the right side and around the start.
+/// Sides are calculated by [`offset_curve`], so loops on the concave side
+/// of sharp bends are not trimmed. Fill the polygon with the nonzero rule to hide them.
+///
+/// # Example
+/// ```
+/// use cubic_spline::{stroke_outline, LineCap, Points, SplineOpts, StrokeOpts};
+///
+/// let pts = Points::from(&[(0.0, 0.0), (10.0, 0.0)]);
+/// let segments = pts.calc_bezier(&SplineOpts::default()).unwrap();
+///
+/// let opts = StrokeOpts::new().width(2.0).cap(LineCap::Square);
+/// let outline: Vec<(f64, f64)> = stroke_outline(&segments, &opts).into();
+///
+/// assert_eq!(outline.first(), Some(&(0.0, 1.0)));
+/// assert!(outline.contains(&(11.0, 1.0)) && outline.contains(&(11.0, -1.0)));
+/// assert!(outline.contains(&(-1.0, -1.0)) && outline.contains(&(-1.0, 1.0)));
+/// ```
+pub fn stroke_outline(segments: &[BezierSegment], opts: &StrokeOpts) -> Points {
+  let (first, last) = match (segments.first(), segments.last()) {
+    (Some(f), Some(l)) => (f, l),
+    _ => return Points::from(Vec::<Point>::new()),
+  };
+
+  let half_width = opts.width / 2.0;
+  let mut out = offset_polyline(segments, half_width, opts);
+
+  cap(
+    &last.p3,
+    unit_tangent(last, 1.0),
+    half_width,
+    opts,
+    &mut out,
+  );
+
+  let mut right = offset_polyline(segments, -half_width, opts);
+  right.reverse();
+  out.extend(right);
+
+  let (tx, ty) = unit_tangent(first, 0.0);
+  cap(&first.p0, (-tx, -ty), half_width, opts, &mut out);
+
+  Points::from(out)
+}
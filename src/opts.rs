@@ -1,4 +1,4 @@
-use crate::Point;
+use crate::{Point, Transform};
 
 ///
 /// Default tension of curve between passed points.
@@ -14,6 +14,43 @@ pub const DEFAULT_TENSION: f64 = 0.5;
 /// [`SplineOpts`]: struct.SplineOpts.html
 pub const DEFAULT_SEGMENTS: u32 = 16;
 
+///
+/// How the curve goes on beyond the first and the last points.
+/// The curve is treated as a function `y(x)` there, so it is continued to the left of the first point
+/// and to the right of the last one.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Extrapolation {
+  ///
+  /// The curve ends at the points, values beyond them are errors.
+  Error,
+
+  ///
+  /// `NaN` beyond the points, so the curve just ends there.
+  NaN,
+
+  ///
+  /// The value of the nearest end point.
+  Constant,
+
+  ///
+  /// A straight line along the tangent of the curve at the nearest end.
+  Linear,
+
+  ///
+  /// The cubic polynomial `y(x)` with the same values and slopes at the ends
+  /// of the nearest piece of the curve, continued beyond the end.
+  Cubic,
+}
+
+// Вручную, а не через `#[default]`, которому нужен Rust 1.62
+#[allow(clippy::derivable_impls)]
+impl Default for Extrapolation {
+  fn default() -> Self {
+    Extrapolation::Error
+  }
+}
+
 ///
 /// A list of options indicating how the spline should be calculated
 ///
@@ -44,14 +81,28 @@ pub const DEFAULT_SEGMENTS: u32 = 16;
 ///
 /// * `hidden_point_at_end` - Same as previous, but affects the end of the graph.
 ///
+/// * `extrapolation` -
+///   How the curve goes on beyond the first and the last points.
+///   Default is [`Extrapolation::Error`].
+///
+/// With the `serde` feature enabled options can be (de)serialized,
+/// missing fields fall back to defaults.
+///
 /// [`DEFAULT_TENSION`]: constant.DEFAULT_TENSION.html
 /// [`DEFAULT_SEGMENTS`]: constant.DEFAULT_SEGMENTS.html
-#[derive(Clone)]
+/// [`Extrapolation::Error`]: enum.Extrapolation.html#variant.Error
+#[derive(Clone, Debug)]
+#[cfg_attr(
+  feature = "serde",
+  derive(serde::Serialize, serde::Deserialize),
+  serde(default)
+)]
 pub struct SplineOpts {
   tension: f64,
   num_of_segments: u32,
   hidden_point_at_start: Option<Point>,
   hidden_point_at_end: Option<Point>,
+  extrapolation: Extrapolation,
 }
 
 impl SplineOpts {
@@ -89,6 +140,48 @@ impl SplineOpts {
     self
   }
 
+  ///
+  /// Sets extrapolation.
+  pub fn extrapolation(mut self, val: Extrapolation) -> Self {
+    self.extrapolation = val;
+    self
+  }
+
+  // Меняет местами скрытые точки в начале и в конце, см. `Points::reverse`
+  pub(crate) fn reversed(mut self) -> Self {
+    std::mem::swap(
+      &mut self.hidden_point_at_start,
+      &mut self.hidden_point_at_end,
+    );
+    self
+  }
+
+  ///
+  /// Applies affine transform to hidden points.
+  /// Use it together with [`Points::transform`].
+  ///
+  /// [`Points::transform`]: struct.Points.html#method.transform
+  pub fn transformed(mut self, t: &Transform) -> Self {
+    if let Some(p) = self.hidden_point_at_start.as_mut() {
+      p.transform(t);
+    }
+    if let Some(p) = self.hidden_point_at_end.as_mut() {
+      p.transform(t);
+    }
+    self
+  }
+
+  // Убирает скрытые точки, например у краёв кусков кривой между разрывами
+  pub(crate) fn without_hidden_points(mut self, at_start: bool, at_end: bool) -> Self {
+    if at_start {
+      self.hidden_point_at_start = None;
+    }
+    if at_end {
+      self.hidden_point_at_end = None;
+    }
+    self
+  }
+
   //
   // Sets tension.
   pub fn get_tension(&self) -> f64 {
@@ -112,6 +205,12 @@ impl SplineOpts {
   pub fn get_hidden_point_at_end(&self) -> Option<&Point> {
     self.hidden_point_at_end.as_ref()
   }
+
+  //
+  // Gets extrapolation.
+  pub fn get_extrapolation(&self) -> Extrapolation {
+    self.extrapolation
+  }
 }
 
 impl Default for SplineOpts {
@@ -132,6 +231,7 @@ impl Default for SplineOpts {
       num_of_segments: DEFAULT_SEGMENTS,
       hidden_point_at_start: None,
       hidden_point_at_end: None,
+      extrapolation: Extrapolation::Error,
     }
   }
 }
@@ -1,5 +1,13 @@
-use crate::calc_spline;
-use crate::{Error, Result, SplineOpts, TryFrom};
+use crate::svg::{knots_from_segments, parse_svg_path, svg_path, DEFAULT_SVG_PRECISION};
+use crate::{bounding_box, extrema_x, extrema_y, inflections, integrate, signed_area};
+use crate::{calc_bezier, calc_poly, calc_spline, closest_point, BezierSegment, PolySegment};
+use crate::{calc_smoothing_spline, resample, Grid, ResampleOpts, SmoothingOpts};
+use crate::{
+  calc_spline_extended, spline_function, Error, Result, SplineFunction, SplineOpts, TryFrom,
+};
+use crate::{calc_spline_with_gaps, split_at_gaps, GapOpts};
+use crate::{clip_spline, BoundingBox, CurvePoint, Extremum, Projection, Rect};
+use crate::{fit_bezier, fit_knots, fit_spline, simplify_rdp, simplify_vw, Fit, FitOpts};
 
 /// Default precision used for point comparison in [`approx_eq`] method.
 ///
@@ -254,6 +262,35 @@ impl Points {
     Ok(Points(v))
   }
 
+  ///
+  /// Takes SVG path data (value of `d` attribute) and returns the points the path goes through,
+  /// so the shape can be smoothed again with [`SplineOpts`].
+  ///
+  /// The tension of each point is derived from the control points of the segment starting at it,
+  /// so a path exported by [`to_svg_path`] without hidden points gives back the same curve.
+  /// Tangents of the spline can only be parallel to the line between neighbouring points,
+  /// therefore arbitrary Bézier curves are approximated.
+  /// To get the exact geometry of the path use [`parse_svg_path`].
+  ///
+  /// # Example
+  /// ```
+  /// use cubic_spline::{Points, Error};
+  ///
+  /// let pts = Points::try_from_svg_path("M 0,0 L 10,5 c 5,5 10,5 15,0 V 20").unwrap();
+  /// let v: Vec<(f64, f64)> = pts.into();
+  ///
+  /// assert_eq!(v, vec![(0.0, 0.0), (10.0, 5.0), (25.0, 5.0), (25.0, 20.0)]);
+  ///
+  /// assert_eq!(Points::try_from_svg_path("M 0,0").unwrap_err(), Error::TooFewPoints);
+  /// ```
+  ///
+  /// [`SplineOpts`]: struct.SplineOpts.html
+  /// [`to_svg_path`]: struct.Points.html#method.to_svg_path
+  /// [`parse_svg_path`]: fn.parse_svg_path.html
+  pub fn try_from_svg_path(d: &str) -> Result<Self> {
+    Points::try_from(knots_from_segments(&parse_svg_path(d)?))
+  }
+
   ///
   /// Inverts the x-value of all points based on the width of the canvas.
   ///
@@ -290,6 +327,73 @@ impl Points {
     self.0.iter_mut().for_each(|p| p.invert_vertically(height));
   }
 
+  ///
+  /// Reverses the order of points so the curve goes in opposite direction
+  /// but keeps exactly the same shape.
+  ///
+  /// Tension of a point affects the curve between it and the next point,
+  /// so tensions are moved along with the pieces of the curve.
+  /// Hidden points swap their roles too, so the options to calculate
+  /// the reversed curve with are returned.
+  ///
+  /// A curve of two points doesn't use `hidden_point_at_end`,
+  /// so if it has `hidden_point_at_start` its shape can not be kept
+  /// and [`Error::NotReversible`] is returned. Points are not changed then.
+  ///
+  /// # Example
+  /// ```
+  /// use cubic_spline::{Error, Point, Points, SplineOpts};
+  ///
+  /// let mut pts = Points::from(&[(0.0, 0.0), (1.0, 2.0), (2.0, 0.0)]);
+  /// pts.get_mut()[0].tension = Some(0.9);
+  /// let opts = SplineOpts::new().hidden_point_at_start((-1.0, 1.0));
+  ///
+  /// let forward = pts.calc_spline(&opts).unwrap();
+  ///
+  /// let reversed_opts = pts.reverse(&opts).unwrap();
+  /// let backward = pts.calc_spline(&reversed_opts).unwrap();
+  ///
+  /// assert_eq!(pts.get_ref()[1].tension, Some(0.9));
+  ///
+  /// let f = forward.get_ref();
+  /// let b = backward.get_ref();
+  /// assert!(f[8].approx_eq(&b[b.len() - 9]));
+  ///
+  /// let mut two = Points::from(&[(0.0, 0.0), (1.0, 2.0)]);
+  /// assert_eq!(two.reverse(&opts).unwrap_err(), Error::NotReversible);
+  /// ```
+  ///
+  /// [`Error::NotReversible`]: enum.Error.html#variant.NotReversible
+  pub fn reverse(&mut self, opts: &SplineOpts) -> Result<SplineOpts> {
+    let len = self.0.len();
+    if len < 2 {
+      return Ok(opts.clone());
+    }
+
+    // Для двух точек скрытая точка в конце не используется, а в начале используется,
+    // поэтому после разворота скрытых точек быть не должно
+    let reversed_opts = if len == 2 {
+      if opts.get_hidden_point_at_start().is_some() {
+        return Err(Error::NotReversible);
+      }
+      opts.clone().without_hidden_points(true, true)
+    } else {
+      opts.clone().reversed()
+    };
+
+    // Натяжение точки `i` относится к сегменту `i -> i + 1`, после разворота
+    // этот сегмент начинается в точке `i + 1`. У последней точки натяжение не используется,
+    // оно остаётся на месте, чтобы двойной разворот ничего не менял.
+    let last_tension = self.0[len - 1].tension;
+    for i in (1..len).rev() {
+      self.0[i].tension = self.0[i - 1].tension;
+    }
+    self.0[0].tension = last_tension;
+
+    self.0.reverse();
+    Ok(reversed_opts)
+  }
+
   ///
   /// The main function that does all the work.
   ///
@@ -314,6 +418,251 @@ impl Points {
   pub fn calc_spline(&self, opts: &SplineOpts) -> Result<Points> {
     calc_spline(&self, opts)
   }
+
+  ///
+  /// Returns the curve as a list of cubic Bézier segments. See [`calc_bezier`].
+  ///
+  /// # Example
+  /// ```
+  /// use cubic_spline::{Points, SplineOpts};
+  ///
+  /// let pts = Points::from(&[(1.0, 1.0), (3.3, 2.7), (5.1, 0.9)]);
+  /// let segments = pts.calc_bezier(&SplineOpts::default()).unwrap();
+  ///
+  /// assert_eq!(segments.len(), 2);
+  /// ```
+  ///
+  /// [`calc_bezier`]: fn.calc_bezier.html
+  pub fn calc_bezier(&self, opts: &SplineOpts) -> Result<Vec<BezierSegment>> {
+    calc_bezier(self, opts)
+  }
+
+  ///
+  /// Returns the curve as a list of polynomial coefficients. See [`calc_poly`].
+  ///
+  /// [`calc_poly`]: fn.calc_poly.html
+  pub fn calc_poly(&self, opts: &SplineOpts) -> Result<Vec<PolySegment>> {
+    calc_poly(self, opts)
+  }
+
+  ///
+  /// Finds the point of the curve nearest to the passed one. See [`closest_point`].
+  ///
+  /// If you call it many times for the same points
+  /// it is better to calculate segments once with [`calc_poly`].
+  ///
+  /// # Example
+  /// ```
+  /// use cubic_spline::{Points, SplineOpts};
+  ///
+  /// let pts = Points::from(&[(0.0, 0.0), (10.0, 10.0), (20.0, 0.0)]);
+  /// let proj = pts.closest_point(&SplineOpts::default(), &(10.0, 15.0).into()).unwrap();
+  ///
+  /// assert!((proj.distance - 5.0).abs() < 1e-9);
+  /// ```
+  ///
+  /// [`closest_point`]: fn.closest_point.html
+  /// [`calc_poly`]: fn.calc_poly.html
+  pub fn closest_point(&self, opts: &SplineOpts, p: &Point) -> Result<Projection> {
+    let segments = calc_poly(self, opts)?;
+    closest_point(&segments, p).ok_or(Error::TooFewPoints)
+  }
+
+  ///
+  /// Returns exact bounding box of the curve. See [`BoundingBox`].
+  ///
+  /// [`BoundingBox`]: struct.BoundingBox.html
+  pub fn bounding_box(&self, opts: &SplineOpts) -> Result<BoundingBox> {
+    let segments = calc_poly(self, opts)?;
+    bounding_box(&segments).ok_or(Error::TooFewPoints)
+  }
+
+  ///
+  /// Returns local extrema of the curve in y-axis. See [`extrema_y`].
+  ///
+  /// [`extrema_y`]: fn.extrema_y.html
+  pub fn extrema_y(&self, opts: &SplineOpts) -> Result<Vec<Extremum>> {
+    calc_poly(self, opts).map(|s| extrema_y(&s))
+  }
+
+  ///
+  /// Returns local extrema of the curve in x-axis. See [`extrema_x`].
+  ///
+  /// [`extrema_x`]: fn.extrema_x.html
+  pub fn extrema_x(&self, opts: &SplineOpts) -> Result<Vec<Extremum>> {
+    calc_poly(self, opts).map(|s| extrema_x(&s))
+  }
+
+  ///
+  /// Returns inflection points of the curve. See [`inflections`].
+  ///
+  /// [`inflections`]: fn.inflections.html
+  pub fn inflections(&self, opts: &SplineOpts) -> Result<Vec<CurvePoint>> {
+    calc_poly(self, opts).map(|s| inflections(&s))
+  }
+
+  ///
+  /// Returns the area under the curve between two x-values. See [`integrate`].
+  ///
+  /// [`integrate`]: fn.integrate.html
+  pub fn integrate(&self, opts: &SplineOpts, x_from: f64, x_to: f64) -> Result<f64> {
+    calc_poly(self, opts).map(|s| integrate(&s, x_from, x_to))
+  }
+
+  ///
+  /// Returns the signed area enclosed by the curve. See [`signed_area`].
+  ///
+  /// [`signed_area`]: fn.signed_area.html
+  pub fn signed_area(&self, opts: &SplineOpts) -> Result<f64> {
+    calc_poly(self, opts).map(|s| signed_area(&s))
+  }
+
+  ///
+  /// Returns parts of the curve visible in the rectangle as separate runs of points.
+  /// See [`clip_spline`].
+  ///
+  /// [`clip_spline`]: fn.clip_spline.html
+  pub fn clip(&self, opts: &SplineOpts, rect: &Rect) -> Result<Vec<Points>> {
+    clip_spline(self, opts, rect)
+  }
+
+  ///
+  /// Calculates points of the smoothing spline. See [`calc_smoothing_spline`].
+  ///
+  /// [`calc_smoothing_spline`]: fn.calc_smoothing_spline.html
+  pub fn calc_smoothing_spline(
+    &self,
+    opts: &SplineOpts,
+    smoothing_opts: &SmoothingOpts,
+  ) -> Result<Points> {
+    calc_smoothing_spline(self, opts, smoothing_opts)
+  }
+
+  ///
+  /// Splits points into runs without gaps. See [`split_at_gaps`].
+  ///
+  /// [`split_at_gaps`]: fn.split_at_gaps.html
+  pub fn split_at_gaps(&self, gap_opts: &GapOpts) -> Vec<Points> {
+    split_at_gaps(self, gap_opts)
+  }
+
+  ///
+  /// Calculates the curve of each run between gaps. See [`calc_spline_with_gaps`].
+  ///
+  /// [`calc_spline_with_gaps`]: fn.calc_spline_with_gaps.html
+  pub fn calc_spline_with_gaps(
+    &self,
+    opts: &SplineOpts,
+    gap_opts: &GapOpts,
+  ) -> Result<Vec<Points>> {
+    calc_spline_with_gaps(self, opts, gap_opts)
+  }
+
+  ///
+  /// Calculates points of the curve continued to `x_from` and `x_to`. See [`calc_spline_extended`].
+  ///
+  /// [`calc_spline_extended`]: fn.calc_spline_extended.html
+  pub fn calc_spline_extended(&self, opts: &SplineOpts, x_from: f64, x_to: f64) -> Result<Points> {
+    calc_spline_extended(self, opts, x_from, x_to)
+  }
+
+  ///
+  /// Calculates the curve as a function `y(x)`. See [`SplineFunction`].
+  ///
+  /// [`SplineFunction`]: struct.SplineFunction.html
+  pub fn spline_function(&self, opts: &SplineOpts) -> Result<SplineFunction> {
+    spline_function(self, opts)
+  }
+
+  ///
+  /// Calculates y-values of the curve at x-values of the grid. See [`resample`].
+  ///
+  /// [`resample`]: fn.resample.html
+  pub fn resample(&self, grid: &Grid, opts: &ResampleOpts) -> Result<Vec<f64>> {
+    resample(self, grid, opts)
+  }
+
+  ///
+  /// Fits the curve to the points as to noisy samples. See [`fit_spline`].
+  ///
+  /// [`fit_spline`]: fn.fit_spline.html
+  pub fn fit(&self, opts: &SplineOpts, fit_opts: &FitOpts) -> Result<Fit> {
+    fit_spline(self, opts, fit_opts)
+  }
+
+  ///
+  /// Fits cubic Bézier curves to the polyline. See [`fit_bezier`].
+  ///
+  /// [`fit_bezier`]: fn.fit_bezier.html
+  pub fn fit_bezier(&self, tolerance: f64) -> Vec<BezierSegment> {
+    fit_bezier(self, tolerance)
+  }
+
+  ///
+  /// Fits the curve to the polyline and returns its knots with tensions. See [`fit_knots`].
+  ///
+  /// [`fit_knots`]: fn.fit_knots.html
+  pub fn fit_knots(&self, tolerance: f64) -> Points {
+    fit_knots(self, tolerance)
+  }
+
+  ///
+  /// Returns simplified polyline. See [`simplify_rdp`].
+  ///
+  /// [`simplify_rdp`]: fn.simplify_rdp.html
+  pub fn simplify_rdp(&self, tolerance: f64) -> Points {
+    simplify_rdp(self, tolerance)
+  }
+
+  ///
+  /// Returns simplified polyline. See [`simplify_vw`].
+  ///
+  /// [`simplify_vw`]: fn.simplify_vw.html
+  pub fn simplify_vw(&self, tolerance: f64) -> Points {
+    simplify_vw(self, tolerance)
+  }
+
+  ///
+  /// Returns the same curve as [`calc_spline`] does, but as a value for `d` attribute
+  /// of the SVG `<path>` element. Each piece of the curve between two neighbouring points
+  /// is represented exactly by one cubic Bézier command, so the result is much smaller
+  /// than a polyline and smooth at any zoom.
+  ///
+  /// Numbers are written with [`DEFAULT_SVG_PRECISION`] digits after the decimal point.
+  /// If the first and the last points are equal the path is closed with `Z`.
+  ///
+  /// # Example
+  /// ```
+  /// use cubic_spline::{Points, SplineOpts};
+  ///
+  /// let pts = Points::from(&[(0.0, 0.0), (3.0, 3.0), (6.0, 0.0)]);
+  /// let d = pts.to_svg_path(&SplineOpts::default()).unwrap();
+  ///
+  /// assert_eq!(d, "M0,0 C0.5,0.5 2,3 3,3 C4,3 5.5,0.5 6,0");
+  /// ```
+  ///
+  /// [`calc_spline`]: struct.Points.html#method.calc_spline
+  /// [`DEFAULT_SVG_PRECISION`]: constant.DEFAULT_SVG_PRECISION.html
+  pub fn to_svg_path(&self, opts: &SplineOpts) -> Result<String> {
+    svg_path(self, opts, DEFAULT_SVG_PRECISION)
+  }
+
+  ///
+  /// The same as [`to_svg_path`] but with specific number of digits after the decimal point.
+  ///
+  /// ```
+  /// use cubic_spline::{Points, SplineOpts};
+  ///
+  /// let pts = Points::from(&[(0.0, 0.0), (1.0, 1.0), (2.0, 0.0)]);
+  /// let d = pts.to_svg_path_with_precision(&SplineOpts::default(), 1).unwrap();
+  ///
+  /// assert_eq!(d, "M0,0 C0.2,0.2 0.7,1 1,1 C1.3,1 1.8,0.2 2,0");
+  /// ```
+  ///
+  /// [`to_svg_path`]: struct.Points.html#method.to_svg_path
+  pub fn to_svg_path_with_precision(&self, opts: &SplineOpts, precision: usize) -> Result<String> {
+    svg_path(self, opts, precision)
+  }
 }
 
 //
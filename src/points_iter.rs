@@ -3,72 +3,59 @@ use crate::{Point, Points, SplineOpts};
 pub(crate) type PointsToCalc<'a> = (&'a Point, &'a Point, &'a Point, &'a Point);
 
 pub(crate) struct PointsIter<'a> {
-  max_index: usize,
   index: usize,
-  points: &'a Points,
-  current: [&'a Point; 4],
-  hidden_point_at_end: Option<&'a Point>,
+  points: &'a [Point],
+  opts: &'a SplineOpts,
 }
 
 impl<'a> PointsIter<'a> {
   pub(crate) fn new(points: &'a Points, opts: &'a SplineOpts) -> Self {
-    let pts = points.get_ref();
-    let curr = &pts[0];
-    let prev = opts.get_hidden_point_at_start().unwrap_or(&curr);
-    let next = &pts[1];
-    let next2 = pts.get(2).unwrap_or(&next);
-
-    let current = [prev, curr, next, next2];
-
     PointsIter {
-      max_index: pts.len() - 1,
       index: 0,
-      points,
-      current,
-      hidden_point_at_end: opts.get_hidden_point_at_end(),
+      points: points.get_ref(),
+      opts,
     }
   }
+}
 
-  fn get_points_to_calc(&self) -> PointsToCalc<'a> {
-    let c = self.current;
-    (&c[0], &c[1], &c[2], &c[3])
-  }
-
-  fn update_current(&mut self) {
-    self.current[0] = self.current[1];
-    self.current[1] = self.current[2];
-    self.current[2] = self.current[3];
-
-    let pts = self.points.get_ref();
-
-    let last = if self.index == self.max_index - 1 {
-      self.hidden_point_at_end.unwrap_or(&self.current[2])
-    } else {
-      &pts[self.index + 2]
-    };
-
-    self.current[3] = last;
-  }
+// Четыре точки, от которых зависит сегмент между `pts[index]` и `pts[index + 1]`.
+// На краях вместо недостающих соседей берутся скрытые точки из опций,
+// а если их нет - крайние точки.
+pub(crate) fn points_to_calc<'a>(
+  pts: &'a [Point],
+  opts: &'a SplineOpts,
+  index: usize,
+) -> PointsToCalc<'a> {
+  let curr = &pts[index];
+  let next = &pts[index + 1];
+
+  let prev = if index == 0 {
+    opts.get_hidden_point_at_start().unwrap_or(curr)
+  } else {
+    &pts[index - 1]
+  };
+
+  // Для кривой из двух точек скрытая точка в конце не используется, как и раньше
+  let next2 = match pts.get(index + 2) {
+    Some(p) => p,
+    None if pts.len() == 2 => next,
+    None => opts.get_hidden_point_at_end().unwrap_or(next),
+  };
+
+  (prev, curr, next, next2)
 }
 
 impl<'a> Iterator for PointsIter<'a> {
   type Item = PointsToCalc<'a>;
 
   fn next(&mut self) -> Option<Self::Item> {
-    if self.index == self.max_index {
+    if self.index + 1 >= self.points.len() {
       return None;
     }
 
-    let points_to_calc = self.get_points_to_calc();
+    let points_to_calc = points_to_calc(self.points, self.opts, self.index);
     self.index += 1;
 
-    // Всё из-за того, что при вызове `self.update_current()` расчитывается следующее состояние
-    // Следующее, потому что в самом начале расчитывается первое состояние,
-    // чтобы не проверять каждый раз на нулевой индекс и не хранить `hidden_point_at_start`
-    if self.index != self.max_index {
-      self.update_current();
-    }
-
     Some(points_to_calc)
   }
 }
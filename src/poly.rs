@@ -0,0 +1,132 @@
+use crate::calc::calc_tangents;
+use crate::{points_iter::PointsIter, BezierSegment, Error, Point, Points, Result, SplineOpts};
+
+///
+/// A piece of the curve between two neighbouring points in power basis:
+/// `x(t) = x[0] + x[1]*t + x[2]*t^2 + x[3]*t^3`, the same for `y`, where `t` is from `0.0` to `1.0`.
+///
+#[derive(Clone, Debug, Default)]
+pub struct PolySegment {
+  ///
+  /// Coefficients `a, b, c, d` of the x-axis polynomial.
+  pub x: [f64; 4],
+
+  ///
+  /// Coefficients `a, b, c, d` of the y-axis polynomial.
+  pub y: [f64; 4],
+}
+
+impl PolySegment {
+  ///
+  /// Calculates the point of the segment at parameter `t`.
+  ///
+  /// # Example
+  /// ```
+  /// use cubic_spline::{PolySegment, Point};
+  ///
+  /// let seg = PolySegment { x: [1.0, 1.0, 0.0, 0.0], y: [0.0, 0.0, 1.0, 0.0] };
+  ///
+  /// assert!(seg.point_at(0.5).approx_eq(&Point::new(1.5, 0.25)));
+  /// ```
+  pub fn point_at(&self, t: f64) -> Point {
+    Point::new(eval(&self.x, t), eval(&self.y, t))
+  }
+}
+
+fn eval(c: &[f64; 4], t: f64) -> f64 {
+  ((c[3] * t + c[2]) * t + c[1]) * t + c[0]
+}
+
+// Коэффициенты при степенях `t` для базиса `c1..c4` из `calc_spline`:
+// c1 = 2t³ - 3t² + 1, c2 = -2t³ + 3t², c3 = t³ - 2t² + t, c4 = t³ - t²
+fn hermite_to_power(p0: f64, p1: f64, t1: f64, t2: f64) -> [f64; 4] {
+  [
+    p0,
+    t1,
+    -3.0 * p0 + 3.0 * p1 - 2.0 * t1 - t2,
+    2.0 * p0 - 2.0 * p1 + t1 + t2,
+  ]
+}
+
+fn bezier_to_power(p0: f64, c1: f64, c2: f64, p3: f64) -> [f64; 4] {
+  [
+    p0,
+    3.0 * (c1 - p0),
+    3.0 * (p0 - 2.0 * c1 + c2),
+    -p0 + 3.0 * c1 - 3.0 * c2 + p3,
+  ]
+}
+
+impl<'a> From<&'a BezierSegment> for PolySegment {
+  fn from(seg: &'a BezierSegment) -> Self {
+    PolySegment {
+      x: bezier_to_power(seg.p0.x, seg.c1.x, seg.c2.x, seg.p3.x),
+      y: bezier_to_power(seg.p0.y, seg.c1.y, seg.c2.y, seg.p3.y),
+    }
+  }
+}
+
+///
+/// Returns the curve constructed by [`calc_spline`] as a list of polynomial coefficients,
+/// one [`PolySegment`] for each pair of neighbouring points.
+///
+/// # Example
+/// ```
+/// use cubic_spline::{Points, SplineOpts};
+///
+/// let pts = Points::from(&[(0.0, 0.0), (3.0, 3.0), (6.0, 0.0)]);
+/// let segments = pts.calc_poly(&SplineOpts::default()).unwrap();
+///
+/// assert_eq!(segments.len(), 2);
+/// assert_eq!(segments[0].x, [0.0, 1.5, 3.0, -1.5]);
+/// assert!(segments[1].point_at(1.0).approx_eq(&(6.0, 0.0).into()));
+/// ```
+///
+/// [`calc_spline`]: fn.calc_spline.html
+/// [`PolySegment`]: struct.PolySegment.html
+pub fn calc_poly(points: &Points, opts: &SplineOpts) -> Result<Vec<PolySegment>> {
+  let points_len = points.get_ref().len();
+
+  if points_len < 2 {
+    return Err(Error::TooFewPoints);
+  }
+
+  let tension_from_opt = opts.get_tension();
+  let mut result = Vec::with_capacity(points_len - 1);
+
+  for pts in PointsIter::new(points, opts) {
+    let (_, curr, next, _) = pts;
+    let ((t1x, t1y), (t2x, t2y)) = calc_tangents(pts, tension_from_opt);
+
+    result.push(PolySegment {
+      x: hermite_to_power(curr.x, next.x, t1x, t2x),
+      y: hermite_to_power(curr.y, next.y, t1y, t2y),
+    });
+  }
+
+  Ok(result)
+}
+
+///
+/// Packs segments into a flat `Vec<f32>` suitable for uploading to GPU buffer.
+/// Each segment takes 8 values: `[ax, bx, cx, dx, ay, by, cy, dy]`,
+/// so it fits into two `vec4`.
+///
+/// # Example
+/// ```
+/// use cubic_spline::{pack_poly, Points, SplineOpts};
+///
+/// let pts = Points::from(&[(0.0, 0.0), (3.0, 3.0), (6.0, 0.0)]);
+/// let packed = pack_poly(&pts.calc_poly(&SplineOpts::default()).unwrap());
+///
+/// assert_eq!(packed.len(), 16);
+/// assert_eq!(&packed[..4], &[0.0, 1.5, 3.0, -1.5]);
+/// ```
+pub fn pack_poly(segments: &[PolySegment]) -> Vec<f32> {
+  let mut res = Vec::with_capacity(segments.len() * 8);
+  segments.iter().for_each(|s| {
+    res.extend(s.x.iter().map(|c| *c as f32));
+    res.extend(s.y.iter().map(|c| *c as f32));
+  });
+  res
+}
@@ -0,0 +1,147 @@
+use crate::roots::{derivative, mul_poly, roots_in};
+use crate::{Point, PolySegment};
+
+///
+/// The result of projecting a point onto the curve.
+/// See [`closest_point`].
+///
+/// [`closest_point`]: fn.closest_point.html
+#[derive(Clone, Debug)]
+pub struct Projection {
+  ///
+  /// Index of the segment (the same as the index of its start point in source points).
+  pub segment: usize,
+
+  ///
+  /// Parameter on the segment from `0.0` to `1.0`.
+  pub t: f64,
+
+  ///
+  /// The nearest point of the curve.
+  pub point: Point,
+
+  ///
+  /// Distance between the passed point and the nearest point of the curve.
+  pub distance: f64,
+}
+
+impl PolySegment {
+  ///
+  /// Finds the point of the segment nearest to the passed one.
+  /// Returns the parameter `t`, the nearest point and the distance to it.
+  ///
+  /// # Example
+  /// ```
+  /// use cubic_spline::{PolySegment, Point};
+  ///
+  /// // the parabola `y = x^2` for x from -1 to 1
+  /// let seg = PolySegment { x: [-1.0, 2.0, 0.0, 0.0], y: [1.0, -4.0, 4.0, 0.0] };
+  /// let (t, p, dist) = seg.closest_point(&Point::new(0.0, -1.0));
+  ///
+  /// assert!((t - 0.5).abs() < 1e-9);
+  /// assert!(p.approx_eq(&Point::new(0.0, 0.0)));
+  /// assert!((dist - 1.0).abs() < 1e-9);
+  /// ```
+  pub fn closest_point(&self, p: &Point) -> (f64, Point, f64) {
+    // Экстремумы квадрата расстояния: (P(t) - p) · P'(t) = 0
+    let rx = [self.x[0] - p.x, self.x[1], self.x[2], self.x[3]];
+    let ry = [self.y[0] - p.y, self.y[1], self.y[2], self.y[3]];
+    let fx = mul_poly(&rx, &derivative(&self.x));
+    let fy = mul_poly(&ry, &derivative(&self.y));
+    let f: Vec<f64> = fx.iter().zip(fy.iter()).map(|(a, b)| a + b).collect();
+
+    let mut best = (0.0, self.point_at(0.0), f64::INFINITY);
+
+    let candidates = roots_in(&f, 0.0, 1.0);
+    for t in [0.0, 1.0].iter().chain(candidates.iter()) {
+      let point = self.point_at(*t);
+      let dist = distance(&point, p);
+      if dist < best.2 {
+        best = (*t, point, dist);
+      }
+    }
+
+    best
+  }
+
+  // Минимально возможное расстояние от точки до сегмента:
+  // кривая лежит внутри выпуклой оболочки своих точек Безье.
+  fn min_distance_bound(&self, p: &Point) -> f64 {
+    let (x, y) = (&self.x, &self.y);
+    let xs = [
+      x[0],
+      x[0] + x[1] / 3.0,
+      x[0] + (2.0 * x[1] + x[2]) / 3.0,
+      x[0] + x[1] + x[2] + x[3],
+    ];
+    let ys = [
+      y[0],
+      y[0] + y[1] / 3.0,
+      y[0] + (2.0 * y[1] + y[2]) / 3.0,
+      y[0] + y[1] + y[2] + y[3],
+    ];
+
+    let axis_gap = |vals: &[f64; 4], v: f64| {
+      let min = vals.iter().cloned().fold(f64::INFINITY, f64::min);
+      let max = vals.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+      (min - v).max(v - max).max(0.0)
+    };
+
+    axis_gap(&xs, p.x).hypot(axis_gap(&ys, p.y))
+  }
+}
+
+fn distance(p1: &Point, p2: &Point) -> f64 {
+  (p1.x - p2.x).hypot(p1.y - p2.y)
+}
+
+///
+/// Finds the point of the curve nearest to the passed one.
+/// Returns `None` if there are no segments.
+///
+/// The search is made on the exact polynomial segments, not on the calculated points.
+/// Segments that are obviously farther than the best found point are skipped,
+/// so it is cheap enough to call it on every mouse move.
+/// Calculate segments once with [`calc_poly`] and reuse them while the points are the same.
+///
+/// # Example
+/// ```
+/// use cubic_spline::{closest_point, Points, Point, SplineOpts};
+///
+/// let pts = Points::from(&[(0.0, 0.0), (10.0, 10.0), (20.0, 0.0)]);
+/// let segments = pts.calc_poly(&SplineOpts::default()).unwrap();
+///
+/// let proj = closest_point(&segments, &Point::new(10.0, 15.0)).unwrap();
+///
+/// assert!(proj.point.approx_eq(&Point::new(10.0, 10.0)));
+/// assert!((proj.distance - 5.0).abs() < 1e-9);
+/// ```
+///
+/// [`calc_poly`]: fn.calc_poly.html
+pub fn closest_point(segments: &[PolySegment], p: &Point) -> Option<Projection> {
+  let mut best: Option<Projection> = None;
+
+  for (i, seg) in segments.iter().enumerate() {
+    if let Some(b) = &best {
+      if seg.min_distance_bound(p) >= b.distance {
+        continue;
+      }
+    }
+
+    let (t, point, distance) = seg.closest_point(p);
+    let is_closer = match &best {
+      Some(b) => distance < b.distance,
+      None => true,
+    };
+    if is_closer {
+      best = Some(Projection {
+        segment: i,
+        t,
+        point,
+        distance,
+      });
+    }
+  }
+
+  best
+}
@@ -0,0 +1,228 @@
+use crate::function::SplineFunction;
+use crate::{Error, Extrapolation, Points, Result};
+use crate::{SmoothingOpts, SmoothingSpline, SplineOpts};
+
+///
+/// X-values to calculate the curve at.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Grid {
+  ///
+  /// `count` values `start`, `start + step`, `start + 2 * step`, ...
+  Regular {
+    ///
+    /// The first value.
+    start: f64,
+
+    ///
+    /// The distance between neighbouring values.
+    step: f64,
+
+    ///
+    /// The number of values.
+    count: usize,
+  },
+
+  ///
+  /// Values in any order.
+  Explicit(Vec<f64>),
+}
+
+impl Grid {
+  ///
+  /// Creates regular grid.
+  pub fn regular(start: f64, step: f64, count: usize) -> Self {
+    Grid::Regular { start, step, count }
+  }
+
+  ///
+  /// Creates grid of passed values.
+  pub fn explicit<I: IntoIterator<Item = f64>>(xs: I) -> Self {
+    Grid::Explicit(xs.into_iter().collect())
+  }
+
+  ///
+  /// Returns all x-values of the grid.
+  pub fn xs(&self) -> Vec<f64> {
+    match self {
+      Grid::Regular { start, step, count } => {
+        (0..*count).map(|i| start + step * i as f64).collect()
+      }
+      Grid::Explicit(xs) => xs.clone(),
+    }
+  }
+}
+
+///
+/// Which curve to calculate the values of.
+#[derive(Clone, Debug)]
+pub enum SplineMode {
+  ///
+  /// The curve calculated by [`calc_spline`] with these options.
+  ///
+  /// [`calc_spline`]: fn.calc_spline.html
+  Cardinal(SplineOpts),
+
+  ///
+  /// The smoothing spline calculated with these options, see [`SmoothingSpline`].
+  ///
+  /// [`SmoothingSpline`]: struct.SmoothingSpline.html
+  Smoothing(SmoothingOpts),
+}
+
+///
+/// A list of options indicating how the curve should be resampled.
+///
+/// ```
+/// use cubic_spline::{Extrapolation, ResampleOpts, SplineMode, SplineOpts};
+///
+/// let opts = ResampleOpts::new()
+///   .mode(SplineMode::Cardinal(SplineOpts::new().tension(0.3)))
+///   .extrapolation(Extrapolation::Constant);
+///
+/// assert_eq!(opts.get_extrapolation(), Extrapolation::Constant);
+/// ```
+/// Options list:
+/// * `mode` - Which curve to use. Default is [`SplineMode::Cardinal`] with default options.
+///
+/// * `extrapolation` -
+///   What to do with x-values outside of the range of points. Default is [`Extrapolation::Error`].
+///   It is used instead of the `extrapolation` of [`SplineMode::Cardinal`] options.
+///   The natural [`SmoothingSpline`] is continued linearly with [`Extrapolation::Cubic`] too.
+///
+/// [`SplineMode::Cardinal`]: enum.SplineMode.html#variant.Cardinal
+/// [`Extrapolation::Error`]: enum.Extrapolation.html#variant.Error
+/// [`Extrapolation::Cubic`]: enum.Extrapolation.html#variant.Cubic
+/// [`SmoothingSpline`]: struct.SmoothingSpline.html
+#[derive(Clone, Debug)]
+pub struct ResampleOpts {
+  mode: SplineMode,
+  extrapolation: Extrapolation,
+}
+
+impl ResampleOpts {
+  ///
+  /// Creates new one with defaults.
+  pub fn new() -> Self {
+    ResampleOpts::default()
+  }
+
+  ///
+  /// Sets mode.
+  pub fn mode(mut self, val: SplineMode) -> Self {
+    self.mode = val;
+    self
+  }
+
+  ///
+  /// Sets extrapolation.
+  pub fn extrapolation(mut self, val: Extrapolation) -> Self {
+    self.extrapolation = val;
+    self
+  }
+
+  //
+  // Gets mode.
+  pub fn get_mode(&self) -> &SplineMode {
+    &self.mode
+  }
+
+  //
+  // Gets extrapolation.
+  pub fn get_extrapolation(&self) -> Extrapolation {
+    self.extrapolation
+  }
+}
+
+impl Default for ResampleOpts {
+  fn default() -> Self {
+    ResampleOpts {
+      mode: SplineMode::Cardinal(SplineOpts::default()),
+      extrapolation: Extrapolation::Error,
+    }
+  }
+}
+
+// Кривая как функция y(x) с продолжением за края
+enum Curve {
+  Cardinal(SplineFunction),
+  Smoothing(SmoothingSpline, Extrapolation),
+}
+
+impl Curve {
+  fn new(points: &Points, opts: &ResampleOpts) -> Result<Self> {
+    let extrapolation = opts.get_extrapolation();
+    match opts.get_mode() {
+      SplineMode::Cardinal(o) => {
+        SplineFunction::new(points, &o.clone().extrapolation(extrapolation)).map(Curve::Cardinal)
+      }
+      SplineMode::Smoothing(o) => {
+        SmoothingSpline::new(points, o).map(|s| Curve::Smoothing(s, extrapolation))
+      }
+    }
+  }
+
+  // `None`, если `x` за краями и продолжать кривую нельзя
+  fn value_at(&self, x: f64) -> Option<f64> {
+    let (s, extrapolation) = match self {
+      Curve::Cardinal(f) => return f.value_at(x),
+      Curve::Smoothing(s, extrapolation) => (s, *extrapolation),
+    };
+    if let Some(y) = s.value_at(x) {
+      return Some(y);
+    }
+
+    let knots = s.knots().get_ref();
+    let (from, to) = (knots[0].x, knots[knots.len() - 1].x);
+    let end = if x < from { from } else { to };
+    match extrapolation {
+      Extrapolation::Error => None,
+      Extrapolation::NaN => Some(f64::NAN),
+      _ if x.is_nan() => Some(f64::NAN),
+      Extrapolation::Constant => s.value_at(end),
+      // у естественного сплайна на краях нет кривизны, поэтому кубическое продолжение прямое
+      Extrapolation::Linear | Extrapolation::Cubic => {
+        Some(s.value_at(end)? + s.derivative_at(end)? * (x - end))
+      }
+    }
+  }
+}
+
+///
+/// Calculates y-values of the curve at x-values of the grid,
+/// e.g. to get values at regular intervals from irregularly spaced samples.
+///
+/// X-values of points should be strictly increasing.
+///
+/// Returns the same errors as [`calc_spline`] or [`SmoothingSpline::new`] depending on the mode,
+/// [`Error::NotIncreasingX`] if x-values are not strictly increasing
+/// and [`Error::OutOfRange`] if some x-value of the grid is outside of the range of points
+/// and [`Extrapolation::Error`] is used.
+///
+/// # Example
+/// ```
+/// use cubic_spline::{resample, Extrapolation, Grid, Points, ResampleOpts};
+///
+/// let pts = Points::from(&[(0.0, 0.0), (3.0, 3.0), (7.0, 7.0), (10.0, 10.0)]);
+/// let opts = ResampleOpts::new().extrapolation(Extrapolation::NaN);
+///
+/// let ys = resample(&pts, &Grid::regular(0.0, 2.5, 6), &opts).unwrap();
+///
+/// assert_eq!(ys.len(), 6);
+/// assert!((ys[2] - 5.0).abs() < 1e-9);
+/// assert!(ys[5].is_nan());
+/// ```
+///
+/// [`calc_spline`]: fn.calc_spline.html
+/// [`SmoothingSpline::new`]: struct.SmoothingSpline.html#method.new
+/// [`Error::NotIncreasingX`]: enum.Error.html#variant.NotIncreasingX
+/// [`Error::OutOfRange`]: enum.Error.html#variant.OutOfRange
+/// [`Extrapolation::Error`]: enum.Extrapolation.html#variant.Error
+pub fn resample(points: &Points, grid: &Grid, opts: &ResampleOpts) -> Result<Vec<f64>> {
+  let curve = Curve::new(points, opts)?;
+
+  grid
+    .xs()
+    .into_iter()
+    .map(|x| curve.value_at(x).ok_or(Error::OutOfRange))
+    .collect()
+}
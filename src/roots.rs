@@ -0,0 +1,125 @@
+// Поиск корней многочлена небольшой степени на отрезке.
+// Коэффициенты идут по возрастанию степени: `c[0] + c[1]*t + c[2]*t^2 + ...`
+
+const EPS: f64 = 1e-12;
+const MAX_ITERATIONS: usize = 100;
+
+pub(crate) fn eval_poly(c: &[f64], t: f64) -> f64 {
+  c.iter().rev().fold(0.0, |acc, k| acc * t + k)
+}
+
+pub(crate) fn derivative(c: &[f64]) -> Vec<f64> {
+  c.iter()
+    .enumerate()
+    .skip(1)
+    .map(|(i, k)| k * i as f64)
+    .collect()
+}
+
+pub(crate) fn mul_poly(a: &[f64], b: &[f64]) -> Vec<f64> {
+  if a.is_empty() || b.is_empty() {
+    return Vec::new();
+  }
+  let mut res = vec![0.0; a.len() + b.len() - 1];
+  for (i, ka) in a.iter().enumerate() {
+    for (j, kb) in b.iter().enumerate() {
+      res[i + j] += ka * kb;
+    }
+  }
+  res
+}
+
+// Определённый интеграл от `a` до `b`
+pub(crate) fn integrate_poly(c: &[f64], a: f64, b: f64) -> f64 {
+  let antiderivative = |t: f64| {
+    c.iter()
+      .enumerate()
+      .rev()
+      .fold(0.0, |acc, (i, k)| (acc + k / (i + 1) as f64) * t)
+  };
+  antiderivative(b) - antiderivative(a)
+}
+
+// Отбрасывает старшие коэффициенты, которые пренебрежимо малы по сравнению с остальными
+fn trim(c: &[f64]) -> &[f64] {
+  let max = c.iter().fold(0.0f64, |m, k| m.max(k.abs()));
+  let mut len = c.len();
+  while len > 0 && c[len - 1].abs() <= max * EPS {
+    len -= 1;
+  }
+  &c[..len]
+}
+
+///
+/// Returns sorted roots of the polynomial within `[from, to]`.
+///
+/// Roots of the derivative split the range into monotonic pieces,
+/// each of them contains at most one root which is found by bisection.
+/// Polynomials that are identically zero have no roots.
+pub(crate) fn roots_in(c: &[f64], from: f64, to: f64) -> Vec<f64> {
+  let c = trim(c);
+
+  match c.len() {
+    0 | 1 => return Vec::new(),
+    2 => {
+      let t = -c[0] / c[1];
+      return if t >= from && t <= to {
+        vec![t]
+      } else {
+        Vec::new()
+      };
+    }
+    _ => {}
+  }
+
+  let mut bounds = vec![from];
+  bounds.extend(roots_in(&derivative(c), from, to));
+  bounds.push(to);
+
+  let mut res: Vec<f64> = Vec::new();
+
+  for w in bounds.windows(2) {
+    if let Some(t) = bisect(c, w[0], w[1]) {
+      let is_dup = matches!(res.last(), Some(last) if (t - last).abs() <= EPS.sqrt());
+      if !is_dup {
+        res.push(t);
+      }
+    }
+  }
+
+  res
+}
+
+fn bisect(c: &[f64], mut lo: f64, mut hi: f64) -> Option<f64> {
+  let mut f_lo = eval_poly(c, lo);
+  let f_hi = eval_poly(c, hi);
+
+  if f_lo == 0.0 {
+    return Some(lo);
+  }
+  if f_hi == 0.0 {
+    return Some(hi);
+  }
+  if f_lo.signum() == f_hi.signum() {
+    return None;
+  }
+
+  for _ in 0..MAX_ITERATIONS {
+    let mid = 0.5 * (lo + hi);
+    if mid <= lo || mid >= hi {
+      break;
+    }
+    let f_mid = eval_poly(c, mid);
+    if f_mid == 0.0 {
+      return Some(mid);
+    }
+    if f_mid.signum() == f_lo.signum() {
+      lo = mid;
+      f_lo = f_mid;
+    } else {
+      hi = mid;
+    }
+  }
+
+  Some(0.5 * (lo + hi))
+}
@@ -0,0 +1,63 @@
+use crate::{Point, Points, TryFrom};
+use serde::de::{self, Deserialize, Deserializer};
+use serde::ser::{Serialize, SerializeStruct, Serializer};
+
+// Все формы, которые принимает `From<..> for Point`.
+#[derive(serde::Deserialize)]
+#[serde(untagged)]
+enum PointRepr {
+  Pair(f64, f64),
+  Full {
+    x: f64,
+    y: f64,
+    #[serde(default)]
+    tension: Option<f64>,
+  },
+}
+
+// Список точек, либо плоский массив `[x,y,x,y...]` как в `try_from_flatten`.
+#[derive(serde::Deserialize)]
+#[serde(untagged)]
+enum PointsRepr {
+  Flatten(Vec<f64>),
+  List(Vec<Point>),
+}
+
+impl Serialize for Point {
+  fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+    let len = if self.tension.is_some() { 3 } else { 2 };
+    let mut s = serializer.serialize_struct("Point", len)?;
+    s.serialize_field("x", &self.x)?;
+    s.serialize_field("y", &self.y)?;
+    if let Some(tension) = self.tension {
+      s.serialize_field("tension", &tension)?;
+    }
+    s.end()
+  }
+}
+
+impl<'de> Deserialize<'de> for Point {
+  fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+    let point = match PointRepr::deserialize(deserializer)? {
+      PointRepr::Pair(x, y) => Point::new(x, y),
+      PointRepr::Full { x, y, tension } => Point { x, y, tension },
+    };
+    Ok(point)
+  }
+}
+
+impl Serialize for Points {
+  fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+    self.get_ref().serialize(serializer)
+  }
+}
+
+impl<'de> Deserialize<'de> for Points {
+  fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+    let points = match PointsRepr::deserialize(deserializer)? {
+      PointsRepr::Flatten(v) => Points::try_from_flatten(&v),
+      PointsRepr::List(v) => Points::try_from(v),
+    };
+    points.map_err(de::Error::custom)
+  }
+}
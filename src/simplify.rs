@@ -0,0 +1,186 @@
+use crate::{Point, Points};
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+
+///
+/// Simplifies the polyline with Ramer–Douglas–Peucker algorithm.
+///
+/// Keeps the first and the last points and only those points that are needed so that
+/// no removed point is farther than `tolerance` from the resulting polyline.
+/// Works for source points and for calculated points of the curve the same way.
+///
+/// # Example
+/// ```
+/// use cubic_spline::{simplify_rdp, Points, SplineOpts};
+///
+/// let pts = Points::from(&[(0.0, 0.0), (50.0, 30.0), (100.0, 0.0), (150.0, 30.0)]);
+/// let curve = pts.calc_spline(&SplineOpts::new().num_of_segments(100)).unwrap();
+///
+/// let simple = simplify_rdp(&curve, 0.5);
+///
+/// assert_eq!(curve.get_ref().len(), 301);
+/// assert!(simple.get_ref().len() < 40);
+/// assert!(simple.get_ref()[0].approx_eq(&curve.get_ref()[0]));
+/// ```
+pub fn simplify_rdp(points: &Points, tolerance: f64) -> Points {
+  let pts = points.get_ref();
+  if pts.len() < 3 {
+    return points.clone();
+  }
+
+  let mut keep = vec![false; pts.len()];
+  keep[0] = true;
+  keep[pts.len() - 1] = true;
+
+  // Рекурсия заменена стеком, чтобы длинные полилинии не переполнили стек вызовов
+  let mut stack = vec![(0, pts.len() - 1)];
+  while let Some((from, to)) = stack.pop() {
+    let farthest = (from + 1..to)
+      .map(|i| (i, distance_to_segment(&pts[i], &pts[from], &pts[to])))
+      .fold(None, |acc: Option<(usize, f64)>, (i, d)| match acc {
+        Some((_, max)) if max >= d => acc,
+        _ => Some((i, d)),
+      });
+
+    if let Some((i, d)) = farthest {
+      if d > tolerance {
+        keep[i] = true;
+        stack.push((from, i));
+        stack.push((i, to));
+      }
+    }
+  }
+
+  Points::from(
+    pts
+      .iter()
+      .zip(keep)
+      .filter(|(_, k)| *k)
+      .map(|(p, _)| p.clone())
+      .collect::<Vec<_>>(),
+  )
+}
+
+///
+/// Simplifies the polyline with Visvalingam–Whyatt algorithm.
+///
+/// Repeatedly removes the point that forms the triangle of the smallest area with its neighbours
+/// until every remaining point forms a triangle of at least `tolerance * tolerance / 2`,
+/// the area of the right triangle with both legs of `tolerance`.
+/// So `tolerance` is in units of points, like in [`simplify_rdp`].
+/// The first and the last points are always kept.
+///
+/// It tends to keep the overall shape better than [`simplify_rdp`] for the same number of points.
+///
+/// # Example
+/// ```
+/// use cubic_spline::{simplify_vw, Points, SplineOpts};
+///
+/// let pts = Points::from(&[(0.0, 0.0), (50.0, 30.0), (100.0, 0.0), (150.0, 30.0)]);
+/// let curve = pts.calc_spline(&SplineOpts::new().num_of_segments(100)).unwrap();
+///
+/// let simple = simplify_vw(&curve, 1.0);
+///
+/// assert!(simple.get_ref().len() < 40);
+/// assert!(simple.get_ref().last().unwrap().approx_eq(&(150.0, 30.0).into()));
+/// ```
+///
+/// [`simplify_rdp`]: fn.simplify_rdp.html
+pub fn simplify_vw(points: &Points, tolerance: f64) -> Points {
+  let pts = points.get_ref();
+  let len = pts.len();
+  if len < 3 {
+    return points.clone();
+  }
+
+  // Двусвязный список по индексам и куча с ленивым удалением устаревших записей
+  let mut prev: Vec<usize> = (0..len).map(|i| i.wrapping_sub(1)).collect();
+  let mut next: Vec<usize> = (1..=len).collect();
+  let mut area = vec![f64::INFINITY; len];
+  let mut heap = BinaryHeap::with_capacity(len);
+
+  for i in 1..len - 1 {
+    area[i] = triangle_area(&pts[i - 1], &pts[i], &pts[i + 1]);
+    heap.push(Candidate(area[i], i));
+  }
+
+  let min_area = tolerance * tolerance / 2.0;
+  let mut removed = vec![false; len];
+  let mut last_area = 0.0f64;
+  while let Some(Candidate(a, i)) = heap.pop() {
+    if removed[i] || a != area[i] {
+      continue;
+    }
+    if a >= min_area {
+      break;
+    }
+
+    // Эффективная площадь не меньше площади уже удалённых точек,
+    // иначе точки удаляются в порядке, не соответствующем их значимости
+    last_area = last_area.max(a);
+    removed[i] = true;
+    let (p, n) = (prev[i], next[i]);
+    next[p] = n;
+    prev[n] = p;
+
+    for &j in &[p, n] {
+      if j == 0 || j == len - 1 {
+        continue;
+      }
+      let new_area = triangle_area(&pts[prev[j]], &pts[j], &pts[next[j]]).max(last_area);
+      area[j] = new_area;
+      heap.push(Candidate(new_area, j));
+    }
+  }
+
+  Points::from(
+    pts
+      .iter()
+      .zip(removed)
+      .filter(|(_, r)| !*r)
+      .map(|(p, _)| p.clone())
+      .collect::<Vec<_>>(),
+  )
+}
+
+// Элемент кучи: площадь и индекс точки. Куча должна отдавать минимальную площадь первой.
+struct Candidate(f64, usize);
+
+impl PartialEq for Candidate {
+  fn eq(&self, other: &Self) -> bool {
+    self.cmp(other) == Ordering::Equal
+  }
+}
+
+impl Eq for Candidate {}
+
+impl PartialOrd for Candidate {
+  fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+    Some(self.cmp(other))
+  }
+}
+
+impl Ord for Candidate {
+  fn cmp(&self, other: &Self) -> Ordering {
+    other
+      .0
+      .total_cmp(&self.0)
+      .then_with(|| other.1.cmp(&self.1))
+  }
+}
+
+fn triangle_area(a: &Point, b: &Point, c: &Point) -> f64 {
+  ((b.x - a.x) * (c.y - a.y) - (c.x - a.x) * (b.y - a.y)).abs() / 2.0
+}
+
+// Расстояние до отрезка, а не до прямой: кривая может возвращаться назад
+fn distance_to_segment(p: &Point, a: &Point, b: &Point) -> f64 {
+  let (dx, dy) = (b.x - a.x, b.y - a.y);
+  let len2 = dx * dx + dy * dy;
+  let t = if len2 == 0.0 {
+    0.0
+  } else {
+    (((p.x - a.x) * dx + (p.y - a.y) * dy) / len2).clamp(0.0, 1.0)
+  };
+  (p.x - a.x - t * dx).hypot(p.y - a.y - t * dy)
+}
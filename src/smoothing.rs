@@ -0,0 +1,303 @@
+use crate::linalg::BandMatrix;
+use crate::{Error, Point, Points, Result, SplineOpts};
+use std::cmp::Ordering;
+
+///
+/// A list of options indicating how the smoothing spline should be calculated.
+///
+/// ```
+/// use cubic_spline::SmoothingOpts;
+///
+/// let opts = SmoothingOpts::new()
+///   .lambda(0.5)
+///   .weights(vec![1.0, 2.0, 1.0]);
+///
+/// assert_eq!(opts.get_lambda(), 0.5);
+/// ```
+/// Options list:
+/// * `lambda` -
+///   Trades off fidelity to the points against roughness of the curve.
+///   Negative values are treated as `0.0`.
+///   `0.0` (default) gives the interpolating natural cubic spline,
+///   the bigger the value the closer the curve is to the least-squares straight line.
+///
+/// * `weights` -
+///   Positive weight of each point. The bigger the weight the closer the curve passes to the point.
+///   All weights are `1.0` if not specified.
+#[derive(Clone, Debug, Default)]
+pub struct SmoothingOpts {
+  lambda: f64,
+  weights: Option<Vec<f64>>,
+}
+
+impl SmoothingOpts {
+  ///
+  /// Creates new one with defaults.
+  pub fn new() -> Self {
+    SmoothingOpts::default()
+  }
+
+  ///
+  /// Sets lambda.
+  pub fn lambda(mut self, val: f64) -> Self {
+    self.lambda = val;
+    self
+  }
+
+  ///
+  /// Sets weights.
+  pub fn weights(mut self, val: Vec<f64>) -> Self {
+    self.weights = Some(val);
+    self
+  }
+
+  //
+  // Gets lambda.
+  pub fn get_lambda(&self) -> f64 {
+    self.lambda
+  }
+
+  //
+  // Gets weights.
+  pub fn get_weights(&self) -> Option<&Vec<f64>> {
+    self.weights.as_ref()
+  }
+}
+
+///
+/// Reinsch smoothing spline.
+///
+/// It is the natural cubic spline `f` that minimizes
+/// `Σ w[i] * (y[i] - f(x[i]))² + lambda * ∫ f''(x)² dx`.
+/// Unlike [`calc_spline`] the curve is a function of x, so x-values of points
+/// should be strictly increasing. The curve has continuous second derivative.
+///
+/// # Example
+/// ```
+/// use cubic_spline::{Points, SmoothingOpts, SmoothingSpline};
+///
+/// let pts = Points::from(&[(0.0, 0.0), (1.0, 1.0), (2.0, 0.0)]);
+///
+/// let interpolating = SmoothingSpline::new(&pts, &SmoothingOpts::default()).unwrap();
+/// assert!((interpolating.value_at(1.0).unwrap() - 1.0).abs() < 1e-9);
+/// assert!((interpolating.value_at(0.5).unwrap() - 0.6875).abs() < 1e-9);
+///
+/// let smooth = SmoothingSpline::new(&pts, &SmoothingOpts::new().lambda(1.0)).unwrap();
+/// assert!(smooth.value_at(1.0).unwrap() < 1.0);
+/// assert!(smooth.value_at(3.0).is_none());
+/// ```
+///
+/// [`calc_spline`]: fn.calc_spline.html
+#[derive(Clone, Debug)]
+pub struct SmoothingSpline {
+  knots: Points,
+  second_derivatives: Vec<f64>,
+}
+
+impl SmoothingSpline {
+  ///
+  /// Calculates the smoothing spline.
+  ///
+  /// Returns [`Error::TooFewPoints`] if there are less than two points,
+  /// [`Error::NotIncreasingX`] if x-values are not strictly increasing and
+  /// [`Error::InvalidWeights`] if the number of weights differs from the number of points
+  /// or some weight is not positive.
+  ///
+  /// [`Error::TooFewPoints`]: enum.Error.html#variant.TooFewPoints
+  /// [`Error::NotIncreasingX`]: enum.Error.html#variant.NotIncreasingX
+  /// [`Error::InvalidWeights`]: enum.Error.html#variant.InvalidWeights
+  pub fn new(points: &Points, opts: &SmoothingOpts) -> Result<Self> {
+    let pts = points.get_ref();
+    let n = pts.len();
+    if n < 2 {
+      return Err(Error::TooFewPoints);
+    }
+    let increasing = |w: &[Point]| w[1].x.partial_cmp(&w[0].x) == Some(Ordering::Greater);
+    if !pts.windows(2).all(increasing) {
+      return Err(Error::NotIncreasingX);
+    }
+
+    let weights = match opts.get_weights() {
+      Some(w) if w.len() != n || w.iter().any(|w| *w <= 0.0 || !w.is_finite()) => {
+        return Err(Error::InvalidWeights);
+      }
+      Some(w) => w.clone(),
+      None => vec![1.0; n],
+    };
+
+    let y: Vec<f64> = pts.iter().map(|p| p.y).collect();
+    let mut second_derivatives = vec![0.0; n];
+
+    // Прямая не имеет кривизны, сглаживать нечего
+    if n == 2 {
+      return Ok(SmoothingSpline {
+        knots: Points::from(pts.iter().map(|p| (p.x, p.y)).collect::<Vec<_>>()),
+        second_derivatives,
+      });
+    }
+
+    // Обозначения из Green & Silverman, "Nonparametric Regression and Generalized Linear Models".
+    // Столбец `m` ленточной матрицы Q (n × n-2) имеет ненулевые элементы в строках m, m+1, m+2.
+    let h: Vec<f64> = pts.windows(2).map(|w| w[1].x - w[0].x).collect();
+    let q = |m: usize| [1.0 / h[m], -1.0 / h[m] - 1.0 / h[m + 1], 1.0 / h[m + 1]];
+
+    let lambda = opts.get_lambda().max(0.0);
+    let size = n - 2;
+    let mut a = BandMatrix::new(size, 2);
+    let mut rhs = vec![0.0; size];
+
+    for m in 0..size {
+      // R
+      a.add(m, m, (h[m] + h[m + 1]) / 3.0);
+      if m + 1 < size {
+        a.add(m + 1, m, h[m + 1] / 6.0);
+      }
+
+      // λ Qᵀ W⁻¹ Q
+      let qm = q(m);
+      for p in m.saturating_sub(2)..=m {
+        let qp = q(p);
+        let v: f64 = (m..=p + 2)
+          .map(|r| qm[r - m] * qp[r - p] / weights[r])
+          .sum();
+        a.add(m, p, lambda * v);
+      }
+
+      rhs[m] = qm.iter().zip(&y[m..m + 3]).map(|(q, y)| q * y).sum();
+    }
+
+    let gamma = a.solve(rhs).ok_or(Error::SmoothingSingular)?;
+
+    // g = y - λ W⁻¹ Q γ
+    let mut g = y;
+    for (m, gm) in gamma.iter().enumerate() {
+      for (k, qk) in q(m).iter().enumerate() {
+        g[m + k] -= lambda * qk * gm / weights[m + k];
+      }
+    }
+    second_derivatives[1..n - 1].copy_from_slice(&gamma);
+
+    Ok(SmoothingSpline {
+      knots: Points::from(pts.iter().zip(g).map(|(p, g)| (p.x, g)).collect::<Vec<_>>()),
+      second_derivatives,
+    })
+  }
+
+  ///
+  /// Gets smoothed points: x-values of source points and values of the curve there.
+  pub fn knots(&self) -> &Points {
+    &self.knots
+  }
+
+  ///
+  /// Gets second derivatives of the curve at the knots. The first and the last are always `0.0`.
+  pub fn second_derivatives(&self) -> &Vec<f64> {
+    &self.second_derivatives
+  }
+
+  ///
+  /// Returns the value of the curve at `x`
+  /// or `None` if `x` is outside of the range of knots.
+  pub fn value_at(&self, x: f64) -> Option<f64> {
+    let knots = self.knots.get_ref();
+    let last = knots.len() - 1;
+    if !(x >= knots[0].x && x <= knots[last].x) {
+      return None;
+    }
+
+    let i = match knots.binary_search_by(|p| p.x.total_cmp(&x)) {
+      Ok(i) => return Some(knots[i].y),
+      Err(i) => i - 1,
+    };
+    Some(self.value_in(i, x))
+  }
+
+  ///
+  /// Returns the slope (the first derivative) of the curve at `x`
+  /// or `None` if `x` is outside of the range of knots.
+  pub fn derivative_at(&self, x: f64) -> Option<f64> {
+    let knots = self.knots.get_ref();
+    let last = knots.len() - 1;
+    if !(x >= knots[0].x && x <= knots[last].x) {
+      return None;
+    }
+
+    let i = match knots.binary_search_by(|p| p.x.total_cmp(&x)) {
+      Ok(i) => i.min(last - 1),
+      Err(i) => i - 1,
+    };
+    Some(self.derivative_in(i, x))
+  }
+
+  ///
+  /// Calculates points of the curve the same way as [`calc_spline`] does:
+  /// `num_of_segments` points between each two knots and the last knot.
+  /// Other options are not used.
+  ///
+  /// [`calc_spline`]: fn.calc_spline.html
+  pub fn calc_points(&self, opts: &SplineOpts) -> Points {
+    let knots = self.knots.get_ref();
+    let num_of_segments = opts.get_num_of_segments();
+    let mut result = Vec::with_capacity((knots.len() - 1) * num_of_segments as usize + 1);
+
+    for (i, w) in knots.windows(2).enumerate() {
+      for s in 0..num_of_segments {
+        let x = w[0].x + (w[1].x - w[0].x) * f64::from(s) / f64::from(num_of_segments);
+        result.push(Point::new(x, self.value_in(i, x)));
+      }
+    }
+
+    let last = &knots[knots.len() - 1];
+    result.push(Point::new(last.x, last.y));
+    Points::from(result)
+  }
+
+  // Значение на отрезке между узлами `i` и `i + 1`
+  fn value_in(&self, i: usize, x: f64) -> f64 {
+    let knots = self.knots.get_ref();
+    let (p0, p1) = (&knots[i], &knots[i + 1]);
+    let (g0, g1) = (self.second_derivatives[i], self.second_derivatives[i + 1]);
+    let h = p1.x - p0.x;
+    let (a, b) = (x - p0.x, p1.x - x);
+
+    (b * p0.y + a * p1.y) / h - a * b / 6.0 * ((1.0 + a / h) * g1 + (1.0 + b / h) * g0)
+  }
+
+  fn derivative_in(&self, i: usize, x: f64) -> f64 {
+    let knots = self.knots.get_ref();
+    let (p0, p1) = (&knots[i], &knots[i + 1]);
+    let (g0, g1) = (self.second_derivatives[i], self.second_derivatives[i + 1]);
+    let h = p1.x - p0.x;
+    let (a, b) = (x - p0.x, p1.x - x);
+
+    (p1.y - p0.y) / h
+      - (b - a) / 6.0 * ((1.0 + a / h) * g1 + (1.0 + b / h) * g0)
+      - a * b / 6.0 * (g1 - g0) / h
+  }
+}
+
+///
+/// Calculates the smoothing spline and its points the same way as [`calc_spline`] does.
+/// See [`SmoothingSpline`].
+///
+/// # Example
+/// ```
+/// use cubic_spline::{calc_smoothing_spline, Points, SmoothingOpts, SplineOpts};
+///
+/// let pts = Points::from(&[(0.0, 0.0), (1.0, 1.2), (2.0, 1.8), (3.0, 3.1)]);
+/// let opts = SplineOpts::new().num_of_segments(10);
+///
+/// let curve = calc_smoothing_spline(&pts, &opts, &SmoothingOpts::new().lambda(0.1)).unwrap();
+///
+/// assert_eq!(curve.get_ref().len(), 31);
+/// ```
+///
+/// [`calc_spline`]: fn.calc_spline.html
+/// [`SmoothingSpline`]: struct.SmoothingSpline.html
+pub fn calc_smoothing_spline(
+  points: &Points,
+  opts: &SplineOpts,
+  smoothing_opts: &SmoothingOpts,
+) -> Result<Points> {
+  SmoothingSpline::new(points, smoothing_opts).map(|s| s.calc_points(opts))
+}
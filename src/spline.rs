@@ -0,0 +1,161 @@
+use crate::calc::calc_segment;
+use crate::points_iter::points_to_calc;
+use crate::{calc_spline, Error, Point, Points, Result, SplineOpts};
+use std::ops::Range;
+
+///
+/// Source points together with options and calculated points of the curve.
+///
+/// Every piece of the curve depends only on four neighbouring source points,
+/// so when one point is moved, inserted or removed only up to four pieces are recalculated.
+/// Each modifying method returns the range of indexes of calculated points that were changed,
+/// which is useful for partial redraws in editors.
+///
+/// # Example
+/// ```
+/// use cubic_spline::{Points, Spline, SplineOpts};
+///
+/// let pts = Points::from(&[(0.0, 0.0), (1.0, 2.0), (2.0, 0.0), (3.0, 2.0), (4.0, 0.0), (5.0, 2.0)]);
+/// let mut spline = Spline::new(pts, SplineOpts::new().num_of_segments(10)).unwrap();
+///
+/// assert_eq!(spline.calculated_points().len(), 51);
+///
+/// let changed = spline.set_point(4, (4.0, -1.0));
+/// assert_eq!(changed, 20..51);
+///
+/// let full = spline.points().calc_spline(spline.opts()).unwrap();
+/// assert_eq!(full.get_ref().len(), spline.calculated_points().len());
+/// ```
+#[derive(Clone, Debug)]
+pub struct Spline {
+  points: Points,
+  opts: SplineOpts,
+  calculated: Vec<Point>,
+}
+
+impl Spline {
+  ///
+  /// Calculates the curve and creates new one.
+  /// Returns the same errors as [`calc_spline`].
+  ///
+  /// [`calc_spline`]: fn.calc_spline.html
+  pub fn new(points: Points, opts: SplineOpts) -> Result<Self> {
+    let calculated = calc_spline(&points, &opts)?.into_inner();
+    Ok(Spline {
+      points,
+      opts,
+      calculated,
+    })
+  }
+
+  ///
+  /// Gets a reference to the source points.
+  pub fn points(&self) -> &Points {
+    &self.points
+  }
+
+  ///
+  /// Gets a reference to the options.
+  pub fn opts(&self) -> &SplineOpts {
+    &self.opts
+  }
+
+  ///
+  /// Gets calculated points of the curve, the same as [`calc_spline`] returns.
+  ///
+  /// [`calc_spline`]: fn.calc_spline.html
+  pub fn calculated_points(&self) -> &Vec<Point> {
+    &self.calculated
+  }
+
+  ///
+  /// Consumes the `Spline`, returning calculated points.
+  pub fn into_calculated_points(self) -> Points {
+    Points::from(self.calculated)
+  }
+
+  ///
+  /// Replaces the source point at `index` and recalculates affected pieces of the curve.
+  /// Returns the range of changed calculated points.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `index` is out of bounds.
+  pub fn set_point<T: Into<Point>>(&mut self, index: usize, point: T) -> Range<usize> {
+    self.points.get_mut()[index] = point.into();
+    self.recalc(index, 0)
+  }
+
+  ///
+  /// Inserts the source point at `index` and recalculates affected pieces of the curve.
+  /// Returns the range of changed calculated points.
+  /// Calculated points after the range are the same, but shifted by `num_of_segments`.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `index > len`.
+  pub fn insert_point<T: Into<Point>>(&mut self, index: usize, point: T) -> Range<usize> {
+    self.points.get_mut().insert(index, point.into());
+    self.recalc(index, 1)
+  }
+
+  ///
+  /// Removes the source point at `index` and recalculates affected pieces of the curve.
+  /// Returns the range of changed calculated points.
+  /// Calculated points after the range are the same, but shifted back by `num_of_segments`.
+  ///
+  /// Returns [`Error::TooFewPoints`] if there are only two points.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `index` is out of bounds.
+  ///
+  /// [`Error::TooFewPoints`]: enum.Error.html#variant.TooFewPoints
+  pub fn remove_point(&mut self, index: usize) -> Result<Range<usize>> {
+    let pts = self.points.get_mut();
+    assert!(index < pts.len(), "index out of bounds");
+    if pts.len() <= 2 {
+      return Err(Error::TooFewPoints);
+    }
+    pts.remove(index);
+    Ok(self.recalc(index, -1))
+  }
+
+  // Пересчитывает сегменты, которые зависят от точки `index`.
+  // Сегмент `k` зависит от точек `k - 1 ..= k + 2`, поэтому меняются сегменты `index - 2 ..= index + 1`.
+  // `len_diff` - на сколько изменилось количество точек.
+  fn recalc(&mut self, index: usize, len_diff: isize) -> Range<usize> {
+    let pts = self.points.get_ref();
+    let last_segment = pts.len() - 2;
+    let num_of_segments = self.opts.get_num_of_segments();
+    let ns = num_of_segments as usize;
+
+    let from = index.saturating_sub(2).min(last_segment);
+    let to = if len_diff < 0 { index } else { index + 1 }.min(last_segment);
+    let old_to = (to as isize - len_diff) as usize;
+
+    let mut chunk = Vec::with_capacity((to + 1 - from) * ns);
+    for k in from..=to {
+      let pts_to_calc = points_to_calc(pts, &self.opts, k);
+      calc_segment(
+        pts_to_calc,
+        self.opts.get_tension(),
+        num_of_segments,
+        &mut chunk,
+      );
+    }
+
+    self.calculated.splice(from * ns..(old_to + 1) * ns, chunk);
+
+    let mut end = (to + 1) * ns;
+    if to == last_segment {
+      let last = &pts[pts.len() - 1];
+      if let Some(p) = self.calculated.last_mut() {
+        *p = Point::new(last.x, last.y);
+      }
+      end += 1;
+    }
+
+    from * ns..end
+  }
+}
@@ -0,0 +1,172 @@
+use crate::roots::roots_in;
+use crate::{BezierSegment, PolySegment};
+
+///
+/// Cuts the curve at parameter `t` of segment with index `segment`
+/// and returns two curves that together reproduce the original one exactly.
+///
+/// If the cut is at the very start (end) of the curve the first (second) curve is empty.
+///
+/// # Panics
+///
+/// Panics if `segment` is out of bounds.
+///
+/// # Example
+/// ```
+/// use cubic_spline::{split_curve, Points, SplineOpts};
+///
+/// let pts = Points::from(&[(0.0, 0.0), (1.0, 2.0), (2.0, 0.0), (3.0, 2.0)]);
+/// let segments = pts.calc_bezier(&SplineOpts::default()).unwrap();
+///
+/// let (left, right) = split_curve(&segments, 1, 0.5);
+///
+/// assert_eq!(left.len(), 2);
+/// assert_eq!(right.len(), 2);
+/// assert!(left[1].p3.approx_eq(&segments[1].point_at(0.5)));
+/// assert!(right[0].p0.approx_eq(&left[1].p3));
+/// ```
+pub fn split_curve(
+  segments: &[BezierSegment],
+  segment: usize,
+  t: f64,
+) -> (Vec<BezierSegment>, Vec<BezierSegment>) {
+  let mut left = segments[..segment].to_vec();
+  let mut right = Vec::with_capacity(segments.len() - segment);
+
+  let seg = &segments[segment];
+  if t <= 0.0 {
+    right.push(seg.clone());
+  } else if t >= 1.0 {
+    left.push(seg.clone());
+  } else {
+    let (l, r) = seg.split(t);
+    left.push(l);
+    right.push(r);
+  }
+
+  right.extend_from_slice(&segments[segment + 1..]);
+  (left, right)
+}
+
+///
+/// Cuts the curve where it reaches the x-value for the first time.
+/// Returns `None` if the curve never reaches it.
+///
+/// # Example
+/// ```
+/// use cubic_spline::{split_curve_at_x, Points, SplineOpts};
+///
+/// let pts = Points::from(&[(0.0, 0.0), (1.0, 2.0), (2.0, 0.0), (3.0, 2.0)]);
+/// let segments = pts.calc_bezier(&SplineOpts::default()).unwrap();
+///
+/// let (left, right) = split_curve_at_x(&segments, 1.5).unwrap();
+/// assert!((left.last().unwrap().p3.x - 1.5).abs() < 1e-9);
+/// assert!((right[0].p0.x - 1.5).abs() < 1e-9);
+///
+/// assert!(split_curve_at_x(&segments, 10.0).is_none());
+/// ```
+pub fn split_curve_at_x(
+  segments: &[BezierSegment],
+  x: f64,
+) -> Option<(Vec<BezierSegment>, Vec<BezierSegment>)> {
+  let (segment, t) = find_x(segments, x, (0, 0.0))?;
+  Some(split_curve(segments, segment, t))
+}
+
+// Первое место, где кривая достигает `x`, не раньше `start`
+pub(crate) fn find_x(
+  segments: &[BezierSegment],
+  x: f64,
+  start: (usize, f64),
+) -> Option<(usize, f64)> {
+  segments
+    .iter()
+    .enumerate()
+    .skip(start.0)
+    .find_map(|(i, seg)| {
+      let poly = PolySegment::from(seg);
+      let c = [poly.x[0] - x, poly.x[1], poly.x[2], poly.x[3]];
+      let from = if i == start.0 { start.1 } else { 0.0 };
+      roots_in(&c, from, 1.0).first().map(|t| (i, *t))
+    })
+}
+
+///
+/// Returns the part of the curve between two places,
+/// each is a pair of the segment index and the parameter on it.
+/// Returns an empty `Vec` if `from` is not before `to`.
+///
+/// # Panics
+///
+/// Panics if any of segment indexes is out of bounds.
+///
+/// # Example
+/// ```
+/// use cubic_spline::{sub_curve, Points, SplineOpts};
+///
+/// let pts = Points::from(&[(0.0, 0.0), (1.0, 2.0), (2.0, 0.0), (3.0, 2.0)]);
+/// let segments = pts.calc_bezier(&SplineOpts::default()).unwrap();
+///
+/// let part = sub_curve(&segments, (0, 0.5), (2, 0.25));
+///
+/// assert_eq!(part.len(), 3);
+/// assert!(part[0].p0.approx_eq(&segments[0].point_at(0.5)));
+/// assert!(part[1].p0.approx_eq(&segments[1].p0));
+/// assert!(part[2].p3.approx_eq(&segments[2].point_at(0.25)));
+/// ```
+pub fn sub_curve(
+  segments: &[BezierSegment],
+  from: (usize, f64),
+  to: (usize, f64),
+) -> Vec<BezierSegment> {
+  let (from_seg, from_t) = (from.0, from.1.clamp(0.0, 1.0));
+  let (to_seg, to_t) = (to.0, to.1.clamp(0.0, 1.0));
+
+  if from_seg > to_seg || (from_seg == to_seg && from_t >= to_t) {
+    return Vec::new();
+  }
+
+  if from_seg == to_seg {
+    let seg = &segments[from_seg];
+    let (_, right) = seg.split(from_t);
+    // параметр `to_t` на правой части
+    let (piece, _) = right.split((to_t - from_t) / (1.0 - from_t));
+    return vec![piece];
+  }
+
+  let (_, mut res) = split_curve(&segments[..=to_seg], from_seg, from_t);
+  let last = res.len() - 1;
+  let (piece, _) = split_curve(&res[last..], 0, to_t);
+  res.truncate(last);
+  res.extend(piece);
+  res
+}
+
+///
+/// Returns the part of the curve between two x-values:
+/// from the place where it reaches `x_from` for the first time
+/// to the place where it reaches `x_to` for the first time after that.
+/// The curve is cut at its ends if it does not reach the values.
+///
+/// # Example
+/// ```
+/// use cubic_spline::{sub_curve_x, Points, SplineOpts};
+///
+/// let pts = Points::from(&[(0.0, 0.0), (1.0, 2.0), (2.0, 0.0), (3.0, 2.0)]);
+/// let segments = pts.calc_bezier(&SplineOpts::default()).unwrap();
+///
+/// let zoomed = sub_curve_x(&segments, 0.5, 2.5);
+///
+/// assert!((zoomed[0].p0.x - 0.5).abs() < 1e-9);
+/// assert!((zoomed.last().unwrap().p3.x - 2.5).abs() < 1e-9);
+/// ```
+pub fn sub_curve_x(segments: &[BezierSegment], x_from: f64, x_to: f64) -> Vec<BezierSegment> {
+  if segments.is_empty() {
+    return Vec::new();
+  }
+
+  let from = find_x(segments, x_from, (0, 0.0)).unwrap_or((0, 0.0));
+  let to = find_x(segments, x_to, from).unwrap_or((segments.len() - 1, 1.0));
+
+  sub_curve(segments, from, to)
+}
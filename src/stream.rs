@@ -0,0 +1,137 @@
+use crate::calc::calc_segment;
+use crate::points_iter::points_to_calc;
+use crate::{Point, SplineOpts};
+
+// Для окончательного расчёта сегмента нужны четыре точки
+const WINDOW: usize = 4;
+
+///
+/// Builds the curve from points that come one by one, e.g. from a live data feed.
+///
+/// A piece of the curve between two points also depends on the point after them,
+/// so it is finalized only when the next-next point arrives.
+/// [`push`] returns calculated points that will not change anymore,
+/// and [`tail`] returns the rest of the curve calculated as if the last pushed point
+/// was the last one.
+///
+/// Finalized points followed by the tail are exactly what [`calc_spline`] returns
+/// for all pushed points. Only the last four points are stored.
+///
+/// # Example
+/// ```
+/// use cubic_spline::{Points, SplineOpts, SplineStream};
+///
+/// let opts = SplineOpts::new().num_of_segments(4);
+/// let mut stream = SplineStream::new(opts.clone());
+///
+/// assert!(stream.push((0.0, 0.0)).is_empty());
+/// assert!(stream.push((1.0, 3.0)).is_empty());
+/// assert_eq!(stream.tail().len(), 5);
+///
+/// let mut result = stream.push((2.0, 1.0));
+/// assert_eq!(result.len(), 4);
+///
+/// result.extend(stream.push((3.0, 2.0)));
+/// result.extend(stream.tail());
+///
+/// let expected = Points::from(&[(0.0, 0.0), (1.0, 3.0), (2.0, 1.0), (3.0, 2.0)])
+///   .calc_spline(&opts)
+///   .unwrap();
+/// assert_eq!(result.len(), expected.get_ref().len());
+/// ```
+///
+/// [`push`]: struct.SplineStream.html#method.push
+/// [`tail`]: struct.SplineStream.html#method.tail
+/// [`calc_spline`]: fn.calc_spline.html
+#[derive(Clone, Debug)]
+pub struct SplineStream {
+  opts: SplineOpts,
+  window: Vec<Point>,
+  len: usize,
+}
+
+impl SplineStream {
+  ///
+  /// Creates new empty stream.
+  pub fn new(opts: SplineOpts) -> Self {
+    SplineStream {
+      opts,
+      window: Vec::with_capacity(WINDOW),
+      len: 0,
+    }
+  }
+
+  ///
+  /// Gets a reference to the options.
+  pub fn opts(&self) -> &SplineOpts {
+    &self.opts
+  }
+
+  ///
+  /// Returns the number of pushed points.
+  pub fn len(&self) -> usize {
+    self.len
+  }
+
+  ///
+  /// Returns `true` if no points were pushed.
+  pub fn is_empty(&self) -> bool {
+    self.len == 0
+  }
+
+  ///
+  /// Adds the next point and returns newly finalized points of the curve.
+  pub fn push<T: Into<Point>>(&mut self, point: T) -> Vec<Point> {
+    if self.window.len() == WINDOW {
+      self.window.remove(0);
+    }
+    self.window.push(point.into());
+    self.len += 1;
+
+    let mut result = Vec::new();
+    if self.len < 3 {
+      return result;
+    }
+
+    // Сегмент перед предпоследней точкой. Если он первый,
+    // то вместо предыдущей точки нужна скрытая точка из опций.
+    let index = if self.len == 3 { 0 } else { 1 };
+    let pts = points_to_calc(&self.window, &self.opts, index);
+    calc_segment(
+      pts,
+      self.opts.get_tension(),
+      self.opts.get_num_of_segments(),
+      &mut result,
+    );
+
+    result
+  }
+
+  ///
+  /// Returns the not yet finalized end of the curve including the last pushed point.
+  /// It changes when the next point is pushed.
+  ///
+  /// If only one point was pushed returns just it.
+  pub fn tail(&self) -> Vec<Point> {
+    let mut result = Vec::new();
+
+    if self.len >= 2 {
+      let from = self.window.len().saturating_sub(3);
+      let window = &self.window[from..];
+      let index = window.len() - 2;
+      let pts = points_to_calc(window, &self.opts, index);
+      calc_segment(
+        pts,
+        self.opts.get_tension(),
+        self.opts.get_num_of_segments(),
+        &mut result,
+      );
+    }
+
+    if let Some(last) = self.window.last() {
+      result.push(Point::new(last.x, last.y));
+    }
+
+    result
+  }
+}
@@ -0,0 +1,301 @@
+use crate::bezier::calc_bezier;
+use crate::{BezierSegment, Error, Point, Points, Result, SplineOpts};
+use std::fmt::Write;
+
+///
+/// Default number of digits after the decimal point in [`to_svg_path`] output.
+///
+/// [`to_svg_path`]: struct.Points.html#method.to_svg_path
+pub const DEFAULT_SVG_PRECISION: usize = 3;
+
+pub(crate) fn svg_path(points: &Points, opts: &SplineOpts, precision: usize) -> Result<String> {
+  let segments = calc_bezier(points, opts)?;
+  let mut d = String::new();
+
+  let first = &segments[0].p0;
+  d.push('M');
+  write_pair(&mut d, first.x, first.y, precision);
+
+  for seg in &segments {
+    d.push_str(" C");
+    write_pair(&mut d, seg.c1.x, seg.c1.y, precision);
+    d.push(' ');
+    write_pair(&mut d, seg.c2.x, seg.c2.y, precision);
+    d.push(' ');
+    write_pair(&mut d, seg.p3.x, seg.p3.y, precision);
+  }
+
+  let pts = points.get_ref();
+  if pts[0].approx_eq(&pts[pts.len() - 1]) {
+    d.push_str(" Z");
+  }
+
+  Ok(d)
+}
+
+fn write_pair(d: &mut String, x: f64, y: f64, precision: usize) {
+  write_num(d, x, precision);
+  d.push(',');
+  write_num(d, y, precision);
+}
+
+// Записывает число без лишних нулей в конце: `12.500` -> `12.5`, `-0.000` -> `0`
+fn write_num(d: &mut String, val: f64, precision: usize) {
+  let start = d.len();
+  let _ = write!(d, "{:.*}", precision, val);
+
+  if d[start..].contains('.') {
+    let trimmed_len = d.trim_end_matches('0').trim_end_matches('.').len();
+    d.truncate(trimmed_len);
+  }
+
+  if &d[start..] == "-0" {
+    d.truncate(start);
+    d.push('0');
+  }
+}
+
+///
+/// Parses SVG path data (value of `d` attribute) into a list of cubic Bézier segments.
+///
+/// Supported commands are `M, L, H, V, C, Q, Z` and their relative versions.
+/// Lines and quadratic curves are converted to the equivalent cubic segments,
+/// so the geometry of the path is preserved exactly.
+/// Only one subpath is allowed.
+///
+/// # Example
+/// ```
+/// use cubic_spline::{parse_svg_path, Error};
+///
+/// let segments = parse_svg_path("M10 10 h 20 Q 40 10 40 30 z").unwrap();
+///
+/// assert_eq!(segments.len(), 3);
+/// assert!(segments[1].p3.approx_eq(&(40.0, 30.0).into()));
+/// assert!(segments[2].p3.approx_eq(&(10.0, 10.0).into()));
+///
+/// assert_eq!(parse_svg_path("L 1 1").unwrap_err(), Error::SvgMissingMoveTo);
+/// assert_eq!(parse_svg_path("M 1 1 A 1 1 0 0 0 2 2").unwrap_err(), Error::SvgUnsupportedCommand);
+/// assert_eq!(parse_svg_path("M 1 1 C 2 2").unwrap_err(), Error::SvgInvalidNumber);
+/// ```
+pub fn parse_svg_path(d: &str) -> Result<Vec<BezierSegment>> {
+  let mut lexer = PathLexer {
+    bytes: d.as_bytes(),
+    pos: 0,
+  };
+
+  let mut segments = Vec::new();
+  let mut start: Option<Point> = None;
+  let mut current = Point::default();
+  let mut prev_cmd: Option<u8> = None;
+
+  while let Some(cmd) = lexer.next_command(prev_cmd)? {
+    let rel = cmd.is_ascii_lowercase();
+    let offset = if rel {
+      current.clone()
+    } else {
+      Point::default()
+    };
+
+    match cmd.to_ascii_uppercase() {
+      b'M' => {
+        if start.is_some() {
+          return Err(Error::SvgMultipleSubpaths);
+        }
+        current = lexer.point(&offset)?;
+        start = Some(current.clone());
+      }
+      b'L' => {
+        let end = lexer.point(&offset)?;
+        segments.push(line(&current, &end));
+        current = end;
+      }
+      b'H' => {
+        let end = Point::new(lexer.number()? + offset.x, current.y);
+        segments.push(line(&current, &end));
+        current = end;
+      }
+      b'V' => {
+        let end = Point::new(current.x, lexer.number()? + offset.y);
+        segments.push(line(&current, &end));
+        current = end;
+      }
+      b'C' => {
+        let c1 = lexer.point(&offset)?;
+        let c2 = lexer.point(&offset)?;
+        let end = lexer.point(&offset)?;
+        segments.push(BezierSegment::new(current, c1, c2, end.clone()));
+        current = end;
+      }
+      b'Q' => {
+        let q = lexer.point(&offset)?;
+        let end = lexer.point(&offset)?;
+        segments.push(quad(&current, &q, &end));
+        current = end;
+      }
+      b'Z' => {
+        // `start` уже есть, т.к. первой командой всегда идёт `M`
+        let first = start.clone().unwrap_or_default();
+        if !current.approx_eq(&first) {
+          segments.push(line(&current, &first));
+        }
+        current = first;
+      }
+      _ => return Err(Error::SvgUnsupportedCommand),
+    }
+
+    prev_cmd = Some(cmd);
+  }
+
+  Ok(segments)
+}
+
+// Узлы кривой с натяжением, подобранным по контрольным точкам.
+// Касательные сплайна в начале и в конце сегмента `i` равны `τ·(next - prev)` и `τ·(next2 - curr)`,
+// а у Безье - `3·(c1 - p0)` и `3·(p3 - c2)`, поэтому `τ` ищется методом наименьших квадратов.
+pub(crate) fn knots_from_segments(segments: &[BezierSegment]) -> Vec<Point> {
+  let mut knots: Vec<Point> = segments.iter().map(|s| s.p0.clone()).collect();
+  if let Some(last) = segments.last() {
+    knots.push(last.p3.clone());
+  }
+
+  let n = knots.len();
+  for (i, seg) in segments.iter().enumerate() {
+    let (curr, next) = (&knots[i], &knots[i + 1]);
+    let prev = if i > 0 { &knots[i - 1] } else { curr };
+    let next2 = if i + 2 < n { &knots[i + 2] } else { next };
+
+    let (t1x, t1y) = (3.0 * (seg.c1.x - seg.p0.x), 3.0 * (seg.c1.y - seg.p0.y));
+    let (t2x, t2y) = (3.0 * (seg.p3.x - seg.c2.x), 3.0 * (seg.p3.y - seg.c2.y));
+    let (d1x, d1y) = (next.x - prev.x, next.y - prev.y);
+    let (d2x, d2y) = (next2.x - curr.x, next2.y - curr.y);
+
+    let den = d1x * d1x + d1y * d1y + d2x * d2x + d2y * d2y;
+    let tension = if den > 0.0 {
+      (t1x * d1x + t1y * d1y + t2x * d2x + t2y * d2y) / den
+    } else {
+      0.0
+    };
+    knots[i].tension = Some(tension);
+  }
+
+  knots
+}
+
+fn line(p0: &Point, p3: &Point) -> BezierSegment {
+  let dx = (p3.x - p0.x) / 3.0;
+  let dy = (p3.y - p0.y) / 3.0;
+  BezierSegment::new(
+    Point::new(p0.x, p0.y),
+    Point::new(p0.x + dx, p0.y + dy),
+    Point::new(p3.x - dx, p3.y - dy),
+    Point::new(p3.x, p3.y),
+  )
+}
+
+fn quad(p0: &Point, q: &Point, p3: &Point) -> BezierSegment {
+  BezierSegment::new(
+    Point::new(p0.x, p0.y),
+    Point::new(
+      p0.x + 2.0 / 3.0 * (q.x - p0.x),
+      p0.y + 2.0 / 3.0 * (q.y - p0.y),
+    ),
+    Point::new(
+      p3.x + 2.0 / 3.0 * (q.x - p3.x),
+      p3.y + 2.0 / 3.0 * (q.y - p3.y),
+    ),
+    Point::new(p3.x, p3.y),
+  )
+}
+
+struct PathLexer<'a> {
+  bytes: &'a [u8],
+  pos: usize,
+}
+
+impl<'a> PathLexer<'a> {
+  fn skip_separators(&mut self) {
+    while let Some(b) = self.bytes.get(self.pos) {
+      if b.is_ascii_whitespace() || *b == b',' {
+        self.pos += 1;
+      } else {
+        break;
+      }
+    }
+  }
+
+  // Возвращает следующую команду. Если вместо неё идут числа,
+  // то повторяется предыдущая команда (после `M` - это `L`)
+  fn next_command(&mut self, prev_cmd: Option<u8>) -> Result<Option<u8>> {
+    self.skip_separators();
+
+    let b = match self.bytes.get(self.pos) {
+      Some(b) => *b,
+      None if prev_cmd.is_none() => return Err(Error::SvgMissingMoveTo),
+      None => return Ok(None),
+    };
+
+    if b.is_ascii_alphabetic() {
+      if prev_cmd.is_none() && b != b'M' && b != b'm' {
+        return Err(Error::SvgMissingMoveTo);
+      }
+      self.pos += 1;
+      return Ok(Some(b));
+    }
+
+    match prev_cmd {
+      None => Err(Error::SvgMissingMoveTo),
+      Some(b'Z') | Some(b'z') => Err(Error::SvgInvalidNumber),
+      Some(b'M') => Ok(Some(b'L')),
+      Some(b'm') => Ok(Some(b'l')),
+      Some(cmd) => Ok(Some(cmd)),
+    }
+  }
+
+  fn point(&mut self, offset: &Point) -> Result<Point> {
+    let x = self.number()?;
+    let y = self.number()?;
+    Ok(Point::new(x + offset.x, y + offset.y))
+  }
+
+  fn number(&mut self) -> Result<f64> {
+    self.skip_separators();
+
+    let start = self.pos;
+    let bytes = self.bytes;
+    let digits = |pos: &mut usize| {
+      let from = *pos;
+      while matches!(bytes.get(*pos), Some(b) if b.is_ascii_digit()) {
+        *pos += 1;
+      }
+      *pos > from
+    };
+
+    if let Some(b'+') | Some(b'-') = bytes.get(self.pos) {
+      self.pos += 1;
+    }
+    let mut has_digits = digits(&mut self.pos);
+    if bytes.get(self.pos) == Some(&b'.') {
+      self.pos += 1;
+      has_digits |= digits(&mut self.pos);
+    }
+    if !has_digits {
+      return Err(Error::SvgInvalidNumber);
+    }
+
+    if let Some(b'e') | Some(b'E') = bytes.get(self.pos) {
+      let mantissa_end = self.pos;
+      self.pos += 1;
+      if let Some(b'+') | Some(b'-') = bytes.get(self.pos) {
+        self.pos += 1;
+      }
+      if !digits(&mut self.pos) {
+        self.pos = mantissa_end;
+      }
+    }
+
+    std::str::from_utf8(&bytes[start..self.pos])
+      .ok()
+      .and_then(|s| s.parse().ok())
+      .ok_or(Error::SvgInvalidNumber)
+  }
+}
@@ -2,6 +2,8 @@ use crate::{Error, Points, SplineOpts, TryFrom};
 
 mod data_flatten;
 mod data_tuples;
+#[cfg(feature = "serde")]
+mod serialization;
 
 #[test]
 fn bench1() {
@@ -99,6 +101,15 @@ fn calc_spline_flatten_err() {
   let two_points = Points::try_from_flatten(&vec![1.1, 2.2, 3.3, 4.4]).unwrap();
   let two_points_res = two_points.calc_spline(&opts).unwrap();
   assert_eq!(two_points_res.get_ref().len(), 17,);
+
+  // для двух точек скрытая точка в конце не используется
+  let with_hidden_end = two_points
+    .calc_spline(&opts.clone().hidden_point_at_end((10.0, -10.0)))
+    .unwrap();
+  assert_eq!(
+    Into::<Vec<f64>>::into(with_hidden_end),
+    Into::<Vec<f64>>::into(two_points_res)
+  );
 }
 
 #[test]
@@ -145,3 +156,1282 @@ fn invert_tst() {
 
   assert_eq!(res1.get_ref().len(), res2.get_ref().len())
 }
+
+#[test]
+fn bezier_tst() {
+  let mut pts = Points::from(&data_tuples::points_arr());
+  pts.get_mut()[3].tension = Some(0.9);
+  let opts = SplineOpts::new()
+    .num_of_segments(10)
+    .hidden_point_at_start((-10.0, 0.0))
+    .hidden_point_at_end((900.0, 300.0));
+
+  let spline = pts.calc_spline(&opts).unwrap();
+  let segments = pts.calc_bezier(&opts).unwrap();
+
+  assert_eq!(segments.len(), pts.get_ref().len() - 1);
+
+  for (i, seg) in segments.iter().enumerate() {
+    for s in 0..10 {
+      let p = seg.point_at(f64::from(s) / 10.0);
+      assert!(p.approx_eq(&spline.get_ref()[i * 10 + s as usize]));
+    }
+  }
+}
+
+#[test]
+fn svg_path_tst() {
+  let opts = SplineOpts::default();
+
+  let closed = Points::from(&[(0.0, 0.0), (10.0, 0.0), (5.0, 5.0), (0.0, 0.0)]);
+  let d = closed.to_svg_path(&opts).unwrap();
+  assert!(d.starts_with("M0,0 C"));
+  assert!(d.ends_with(" Z"));
+  assert_eq!(d.matches('C').count(), 3);
+
+  let open = Points::from(&[(0.0, 0.0), (-1.0, 0.000_1)]);
+  assert_eq!(
+    open.to_svg_path(&opts).unwrap(),
+    "M0,0 C-0.167,0 -0.833,0 -1,0"
+  );
+
+  assert_eq!(
+    Points::from(&[(1.0, 1.0)]).to_svg_path(&opts).unwrap_err(),
+    Error::TooFewPoints
+  );
+}
+
+#[test]
+fn poly_tst() {
+  let mut pts = Points::from(&data_tuples::points_arr());
+  pts.get_mut()[5].tension = Some(0.2);
+  let opts = SplineOpts::new().hidden_point_at_end((900.0, 300.0));
+
+  let spline = pts.calc_spline(&opts).unwrap();
+  let poly = pts.calc_poly(&opts).unwrap();
+  let bezier = pts.calc_bezier(&opts).unwrap();
+
+  for (i, (p, b)) in poly.iter().zip(bezier.iter()).enumerate() {
+    let from_bezier = crate::PolySegment::from(b);
+    for s in 0..16 {
+      let t = f64::from(s) / 16.0;
+      let expected = &spline.get_ref()[i * 16 + s as usize];
+      assert!(p.point_at(t).approx_eq(expected));
+      assert!(from_bezier.point_at(t).approx_eq(expected));
+    }
+  }
+}
+
+#[test]
+fn parse_svg_path_tst() {
+  use crate::parse_svg_path;
+
+  let pts = Points::from(&data_tuples::points_arr());
+  let opts = SplineOpts::new().tension(0.3);
+  let d = pts.to_svg_path_with_precision(&opts, 10).unwrap();
+
+  let parsed = parse_svg_path(&d).unwrap();
+  let expected = pts.calc_bezier(&opts).unwrap();
+  assert_eq!(parsed.len(), expected.len());
+  for (p, e) in parsed.iter().zip(expected.iter()) {
+    assert!(p.c1.approx_eq(&e.c1) && p.c2.approx_eq(&e.c2) && p.p3.approx_eq(&e.p3));
+  }
+
+  // натяжение узлов берётся из контрольных точек, поэтому кривая сохраняется
+  let knots = Points::try_from_svg_path(&d).unwrap();
+  assert!(knots.get_ref()[..3]
+    .iter()
+    .all(|p| (p.tension.unwrap() - 0.3).abs() < 1e-9));
+  let restored = knots.calc_bezier(&SplineOpts::default()).unwrap();
+  for (r, e) in restored.iter().zip(expected.iter()) {
+    assert!(r.c1.approx_eq(&e.c1) && r.c2.approx_eq(&e.c2) && r.p3.approx_eq(&e.p3));
+  }
+
+  let compact = Points::try_from_svg_path("m1.5.5-1-1e1 2E-1,3 1 1 0 .5l1-1").unwrap();
+  assert_eq!(
+    Into::<Vec<(f64, f64)>>::into(compact),
+    vec![
+      (1.5, 0.5),
+      (0.5, -9.5),
+      (0.7, -6.5),
+      (1.7, -5.5),
+      (1.7, -5.0),
+      (2.7, -6.0)
+    ]
+  );
+
+  assert_eq!(parse_svg_path("").unwrap_err(), Error::SvgMissingMoveTo);
+  assert_eq!(
+    parse_svg_path("M 0 0 L 1 1 M 2 2 L 3 3").unwrap_err(),
+    Error::SvgMultipleSubpaths
+  );
+  assert_eq!(
+    parse_svg_path("M 0 0 L 1 x").unwrap_err(),
+    Error::SvgInvalidNumber
+  );
+  assert_eq!(
+    parse_svg_path("M 0 0 L 1 1 Z 3").unwrap_err(),
+    Error::SvgInvalidNumber
+  );
+}
+
+#[test]
+fn closest_point_tst() {
+  let pts = Points::from(&data_tuples::points_arr());
+  let opts = SplineOpts::default();
+  let segments = pts.calc_poly(&opts).unwrap();
+  let spline = pts
+    .calc_spline(&opts.clone().num_of_segments(2000))
+    .unwrap();
+
+  for q in &[
+    (100.0, 100.0),
+    (400.0, 20.0),
+    (-50.0, 300.0),
+    (655.0, 300.0),
+  ] {
+    let q = crate::Point::from(*q);
+    let proj = crate::closest_point(&segments, &q).unwrap();
+
+    let brute = spline
+      .get_ref()
+      .iter()
+      .map(|p| (p.x - q.x).hypot(p.y - q.y))
+      .fold(f64::INFINITY, f64::min);
+
+    assert!(proj.distance <= brute + 1e-9);
+    assert!(brute - proj.distance < 1e-2);
+    assert!(segments[proj.segment]
+      .point_at(proj.t)
+      .approx_eq(&proj.point));
+  }
+
+  assert!(crate::closest_point(&[], &(1.0, 1.0).into()).is_none());
+}
+
+#[test]
+fn bounding_box_tst() {
+  let pts = Points::from(&data_tuples::points_arr());
+  let opts = SplineOpts::new().tension(0.8);
+  let bbox = pts.bounding_box(&opts).unwrap();
+  let segments = pts.calc_poly(&opts).unwrap();
+
+  let spline = pts.calc_spline(&opts.num_of_segments(1000)).unwrap();
+  let (mut min_y, mut max_y) = (f64::INFINITY, f64::NEG_INFINITY);
+  for p in spline.get_ref() {
+    assert!(p.x >= bbox.min_x.value - 1e-9 && p.x <= bbox.max_x.value + 1e-9);
+    assert!(p.y >= bbox.min_y.value - 1e-9 && p.y <= bbox.max_y.value + 1e-9);
+    min_y = min_y.min(p.y);
+    max_y = max_y.max(p.y);
+  }
+
+  assert!(bbox.max_y.value > 375.0);
+  assert!(min_y - bbox.min_y.value < 1e-3);
+  assert!(bbox.max_y.value - max_y < 1e-3);
+
+  let at_max = segments[bbox.max_y.segment].point_at(bbox.max_y.t);
+  assert!((at_max.y - bbox.max_y.value).abs() < 1e-9);
+
+  assert!(crate::bounding_box(&[]).is_none());
+}
+
+#[test]
+fn extrema_tst() {
+  use crate::ExtremumKind;
+
+  let pts = Points::from(&data_tuples::points_arr());
+  let opts = SplineOpts::default();
+  let spline = pts.calc_spline(&opts.clone().num_of_segments(100)).unwrap();
+  let sampled = spline.get_ref();
+
+  let mut expected = Vec::new();
+  for w in sampled.windows(3) {
+    if w[1].y > w[0].y && w[1].y > w[2].y {
+      expected.push(ExtremumKind::Max);
+    } else if w[1].y < w[0].y && w[1].y < w[2].y {
+      expected.push(ExtremumKind::Min);
+    }
+  }
+
+  let extrema = pts.extrema_y(&opts).unwrap();
+  let kinds: Vec<ExtremumKind> = extrema.iter().map(|e| e.kind).collect();
+  assert_eq!(kinds, expected);
+
+  let segments = pts.calc_poly(&opts).unwrap();
+  for e in &extrema {
+    let d = crate::roots::derivative(&segments[e.segment].y);
+    assert!(crate::roots::eval_poly(&d, e.t).abs() < 1e-6);
+  }
+
+  let straight = Points::from(&[(0.0, 0.0), (1.0, 1.0), (2.0, 2.0)]);
+  assert!(straight.inflections(&opts).unwrap().is_empty());
+  assert!(straight.extrema_x(&opts).unwrap().is_empty());
+}
+
+#[test]
+fn intersect_tst() {
+  use crate::{intersect, intersect_line_segment, self_intersections, BezierSegment, Point};
+
+  let opts = SplineOpts::default();
+  let a = Points::from(&data_tuples::points_arr());
+  let mut b = Points::from(&data_tuples::points_arr());
+  b.invert_vertically(400.0);
+
+  let sa = a.calc_bezier(&opts).unwrap();
+  let sb = b.calc_bezier(&opts).unwrap();
+
+  // кривая и её отражение пересекаются на прямой y = 200
+  let crossings = intersect(&sa, &sb);
+  let on_line = intersect_line_segment(&sa, &(0.0, 200.0).into(), &(870.0, 200.0).into());
+  assert_eq!(crossings.len(), on_line.len());
+  assert!(!crossings.is_empty());
+  for (c, l) in crossings.iter().zip(on_line.iter()) {
+    assert!((c.point.y - 200.0).abs() < 1e-6);
+    assert!(c.point.approx_eq(&l.point));
+    assert!(sb[c.segment_b].point_at(c.t_b).approx_eq(&c.point));
+  }
+
+  assert!(self_intersections(&sa).is_empty());
+
+  let looped = BezierSegment::new(
+    Point::new(0.0, 0.0),
+    Point::new(15.0, 10.0),
+    Point::new(-5.0, 10.0),
+    Point::new(10.0, 0.0),
+  );
+  let loops = self_intersections(std::slice::from_ref(&looped));
+  assert_eq!(loops.len(), 1);
+  assert!(looped.point_at(loops[0].t_b).approx_eq(&loops[0].point));
+  assert!((loops[0].point.x - 5.0).abs() < 1e-6);
+
+  // у замкнутой кривой место стыка концов не считается пересечением
+  let closed_opts = SplineOpts::new()
+    .hidden_point_at_start((0.0, 10.0))
+    .hidden_point_at_end((10.0, 0.0));
+  let square = Points::from(&[
+    (0.0, 0.0),
+    (10.0, 0.0),
+    (10.0, 10.0),
+    (0.0, 10.0),
+    (0.0, 0.0),
+  ]);
+  assert!(self_intersections(&square.calc_bezier(&closed_opts).unwrap()).is_empty());
+
+  let bow_tie_opts = SplineOpts::new()
+    .hidden_point_at_start((0.0, 10.0))
+    .hidden_point_at_end((10.0, 10.0));
+  let bow_tie = Points::from(&[
+    (0.0, 0.0),
+    (10.0, 10.0),
+    (10.0, 0.0),
+    (0.0, 10.0),
+    (0.0, 0.0),
+  ]);
+  let crossings = self_intersections(&bow_tie.calc_bezier(&bow_tie_opts).unwrap());
+  assert_eq!(crossings.len(), 1);
+  assert!(crossings[0].point.approx_eq(&Point::new(5.0, 5.0)));
+}
+
+#[test]
+fn area_tst() {
+  let pts = Points::from(&data_tuples::points_arr());
+  let opts = SplineOpts::default();
+  let spline = pts.calc_spline(&opts.clone().num_of_segments(500)).unwrap();
+  let sampled = spline.get_ref();
+
+  let trapezoid: f64 = sampled
+    .windows(2)
+    .map(|w| (w[1].x - w[0].x) * (w[0].y + w[1].y) / 2.0)
+    .sum();
+  let exact = pts.integrate(&opts, 0.0, 870.0).unwrap();
+  assert!((exact - trapezoid).abs() < 1e-2);
+
+  let part = pts.integrate(&opts, 100.0, 400.0).unwrap();
+  let rest =
+    pts.integrate(&opts, 400.0, 870.0).unwrap() + pts.integrate(&opts, 0.0, 100.0).unwrap();
+  assert!((part + rest - exact).abs() < 1e-6);
+
+  let (first, last) = (&sampled[0], &sampled[sampled.len() - 1]);
+  let shoelace = (sampled
+    .windows(2)
+    .map(|w| w[0].x * w[1].y - w[1].x * w[0].y)
+    .sum::<f64>()
+    + last.x * first.y
+    - first.x * last.y)
+    / 2.0;
+  let area = pts.signed_area(&opts).unwrap();
+  assert!((area - shoelace).abs() < 1e-2);
+}
+
+#[test]
+fn spline_incremental_tst() {
+  use crate::Spline;
+
+  let opts = SplineOpts::new()
+    .num_of_segments(7)
+    .hidden_point_at_start((-20.0, 0.0))
+    .hidden_point_at_end((900.0, 100.0));
+  let mut spline = Spline::new(Points::from(&data_tuples::points_arr()), opts).unwrap();
+
+  let check = |spline: &Spline, changed: std::ops::Range<usize>, prev: &[crate::Point]| {
+    let full = spline.points().calc_spline(spline.opts()).unwrap();
+    let calculated = spline.calculated_points();
+    assert_eq!(full.get_ref().len(), calculated.len());
+    for (a, b) in full.get_ref().iter().zip(calculated.iter()) {
+      assert!(a.x == b.x && a.y == b.y);
+    }
+    for (i, p) in calculated.iter().enumerate().take(changed.start) {
+      assert!(p.approx_eq(&prev[i]));
+    }
+    let shift = calculated.len() as isize - prev.len() as isize;
+    for i in changed.end..calculated.len() {
+      assert!(calculated[i].approx_eq(&prev[(i as isize - shift) as usize]));
+    }
+  };
+
+  for &i in &[0, 1, 4, 8, 9] {
+    let prev = spline.calculated_points().clone();
+    let changed = spline.set_point(i, (i as f64 * 10.0, 55.0));
+    check(&spline, changed, &prev);
+  }
+
+  for &i in &[0, 3, 11, 5] {
+    let prev = spline.calculated_points().clone();
+    let changed = spline.insert_point(i, crate::Point::with_tension(1.0, 2.0, 0.1));
+    check(&spline, changed, &prev);
+  }
+
+  for &i in &[0, 12, 6, 2, 0, 0, 0, 0, 0, 0, 0, 1] {
+    let prev = spline.calculated_points().clone();
+    let changed = spline.remove_point(i).unwrap();
+    check(&spline, changed, &prev);
+  }
+
+  assert_eq!(spline.points().get_ref().len(), 2);
+  assert_eq!(spline.remove_point(0).unwrap_err(), Error::TooFewPoints);
+
+  let prev = spline.calculated_points().clone();
+  let changed = spline.insert_point(2, (5.0, 5.0));
+  check(&spline, changed, &prev);
+}
+
+#[test]
+fn stream_tst() {
+  use crate::SplineStream;
+
+  let src = data_tuples::points_arr();
+  let opts = SplineOpts::new()
+    .num_of_segments(9)
+    .hidden_point_at_start((-20.0, 0.0))
+    .hidden_point_at_end((900.0, 100.0));
+
+  let mut stream = SplineStream::new(opts.clone());
+  let mut finalized = Vec::new();
+
+  for (i, p) in src.iter().enumerate() {
+    finalized.extend(stream.push(p));
+    assert_eq!(stream.len(), i + 1);
+
+    let mut all = finalized.clone();
+    all.extend(stream.tail());
+
+    if i == 0 {
+      assert_eq!(all.len(), 1);
+      continue;
+    }
+
+    let expected = Points::from(&src[..=i]).calc_spline(&opts).unwrap();
+    assert_eq!(all.len(), expected.get_ref().len());
+    for (a, b) in all.iter().zip(expected.get_ref().iter()) {
+      assert!(a.x == b.x && a.y == b.y);
+    }
+  }
+}
+
+#[test]
+fn split_tst() {
+  use crate::{split_curve, sub_curve, sub_curve_x};
+
+  let pts = Points::from(&data_tuples::points_arr());
+  let segments = pts.calc_bezier(&SplineOpts::default()).unwrap();
+
+  let (left, right) = split_curve(&segments, 4, 0.3);
+  assert_eq!(left.len() + right.len(), segments.len() + 1);
+  for s in 0..=10 {
+    let t = f64::from(s) / 10.0;
+    assert!(left[4]
+      .point_at(t)
+      .approx_eq(&segments[4].point_at(0.3 * t)));
+    assert!(right[0]
+      .point_at(t)
+      .approx_eq(&segments[4].point_at(0.3 + 0.7 * t)));
+  }
+
+  let (left, right) = split_curve(&segments, 0, 0.0);
+  assert!(left.is_empty());
+  assert_eq!(right.len(), segments.len());
+
+  let part = sub_curve(&segments, (3, 0.2), (3, 0.6));
+  assert_eq!(part.len(), 1);
+  assert!(part[0].point_at(0.5).approx_eq(&segments[3].point_at(0.4)));
+
+  assert!(sub_curve(&segments, (3, 0.6), (3, 0.2)).is_empty());
+  assert_eq!(sub_curve(&segments, (2, 1.0), (5, 0.0)).len(), 2);
+
+  let whole = sub_curve_x(&segments, -100.0, 10_000.0);
+  assert_eq!(whole.len(), segments.len());
+}
+
+#[test]
+fn reverse_tst() {
+  let with_start = SplineOpts::new()
+    .tension(0.4)
+    .hidden_point_at_start((-20.0, 0.0))
+    .hidden_point_at_end((900.0, 100.0));
+  let only_end = SplineOpts::new()
+    .tension(0.4)
+    .hidden_point_at_end((900.0, 100.0));
+
+  for opts in &[with_start, only_end] {
+    for len in 2..=5 {
+      let mut pts = Points::from(&data_tuples::points_arr()[..len]);
+      pts.get_mut()[0].tension = Some(0.9);
+      pts.get_mut()[len - 1].tension = Some(0.1);
+
+      let forward = pts.calc_bezier(opts).unwrap();
+      let original = pts.clone();
+
+      if len == 2 && opts.get_hidden_point_at_start().is_some() {
+        // скрытая точка в конце у двух точек не используется, форму сохранить нельзя
+        assert_eq!(pts.reverse(opts).unwrap_err(), Error::NotReversible);
+      } else {
+        let reversed_opts = pts.reverse(opts).unwrap();
+        let backward = pts.calc_bezier(&reversed_opts).unwrap();
+
+        assert_eq!(forward.len(), backward.len());
+        for (f, b) in forward.iter().zip(backward.iter().rev()) {
+          assert!(f.p0.approx_eq(&b.p3) && f.p3.approx_eq(&b.p0));
+          assert!(f.c1.approx_eq(&b.c2) && f.c2.approx_eq(&b.c1));
+        }
+
+        pts.reverse(&reversed_opts).unwrap();
+      }
+
+      for (a, b) in pts.get_ref().iter().zip(original.get_ref().iter()) {
+        assert!(a.approx_eq(b));
+        assert_eq!(a.tension, b.tension);
+      }
+    }
+  }
+}
+
+#[test]
+fn transform_tst() {
+  use crate::{Point, Transform};
+
+  let opts = SplineOpts::new()
+    .tension(0.7)
+    .num_of_segments(7)
+    .hidden_point_at_start((-20.0, 0.0))
+    .hidden_point_at_end((900.0, 100.0));
+
+  let t = Transform::rotate(0.3)
+    .then_skew(0.2, -0.1)
+    .then_scale(1.5, -2.0)
+    .then_translate(-40.0, 12.5);
+
+  let mut pts = Points::from(&data_tuples::points_arr());
+  let mut curve = pts.calc_spline(&opts).unwrap();
+  let mut segments = pts.calc_poly(&opts).unwrap();
+
+  curve.transform(&t);
+  segments.iter_mut().for_each(|s| s.transform(&t));
+  pts.transform(&t);
+
+  let transformed_opts = opts.transformed(&t);
+  let expected = pts.calc_spline(&transformed_opts).unwrap();
+  for (a, b) in curve.get_ref().iter().zip(expected.get_ref().iter()) {
+    assert!(a.approx_eq_with_precision(b, 1e-9));
+  }
+
+  let expected = pts.calc_poly(&transformed_opts).unwrap();
+  for (a, b) in segments.iter().zip(expected.iter()) {
+    for &t in &[0.0, 0.3, 1.0] {
+      assert!(a.point_at(t).approx_eq_with_precision(&b.point_at(t), 1e-9));
+    }
+  }
+
+  let inv = t.inverse().unwrap();
+  assert!(t
+    .then(&inv)
+    .apply(&Point::new(3.0, -7.0))
+    .approx_eq(&Point::new(3.0, -7.0)));
+  assert!(Transform::scale(1.0, 0.0).inverse().is_none());
+}
+
+#[test]
+fn offset_tst() {
+  use crate::{closest_point, offset_curve, stroke_outline, LineCap, LineJoin, StrokeOpts};
+
+  let pts = Points::from(&data_tuples::points_arr());
+  let opts = SplineOpts::default();
+  let segments = pts.calc_bezier(&opts).unwrap();
+  let poly = pts.calc_poly(&opts).unwrap();
+
+  let stroke = StrokeOpts::new().tolerance(0.01);
+  let offset = offset_curve(&segments, 5.0, &stroke);
+  assert!(offset.get_ref().len() > segments.len());
+
+  // точки смещённой кривой не дальше от исходной кривой, чем на `distance`
+  for p in offset.get_ref() {
+    let proj = closest_point(&poly, p).unwrap();
+    assert!(proj.distance <= 5.0 + 1e-6);
+  }
+  let on_offset = offset.get_ref()[offset.get_ref().len() / 2].clone();
+  assert!((closest_point(&poly, &on_offset).unwrap().distance - 5.0).abs() < 1e-6);
+
+  // прямые углы при нулевом натяжении
+  let corner = Points::from(&[(0.0, 0.0), (10.0, 0.0), (10.0, 10.0)]);
+  let corner_segments = corner.calc_bezier(&SplineOpts::new().tension(0.0)).unwrap();
+
+  let miter = StrokeOpts::new().width(2.0).join(LineJoin::Miter(4.0));
+  let outer = offset_curve(&corner_segments, -1.0, &miter);
+  assert!(outer
+    .get_ref()
+    .iter()
+    .any(|p| p.approx_eq(&(11.0, -1.0).into())));
+
+  let bevel = miter.clone().join(LineJoin::Bevel);
+  let outer = offset_curve(&corner_segments, -1.0, &bevel);
+  assert!(!outer
+    .get_ref()
+    .iter()
+    .any(|p| p.approx_eq(&(11.0, -1.0).into())));
+
+  let round = miter.clone().join(LineJoin::Round).cap(LineCap::Round);
+  let outline = stroke_outline(&corner_segments, &round);
+  for p in outline.get_ref() {
+    let proj = closest_point(
+      &corner.calc_poly(&SplineOpts::new().tension(0.0)).unwrap(),
+      p,
+    )
+    .unwrap();
+    assert!((proj.distance - 1.0).abs() < 1e-6 || proj.distance < 1.0);
+  }
+  assert!(stroke_outline(&[], &round).get_ref().is_empty());
+}
+
+#[test]
+fn viewport_tst() {
+  use crate::{Point, Scale, Viewport};
+
+  let pts = Points::from(&data_tuples::points_arr());
+  let opts = SplineOpts::new().num_of_segments(8);
+  let curve = pts.calc_spline(&opts).unwrap();
+
+  let viewport = Viewport::new(640.0, 480.0).padding(20.0).fit(&curve);
+  let on_canvas = viewport.map_points(&curve);
+  for p in on_canvas.get_ref() {
+    assert!(p.x >= 20.0 - 1e-9 && p.x <= 620.0 + 1e-9);
+    assert!(p.y >= 20.0 - 1e-9 && p.y <= 460.0 + 1e-9);
+  }
+
+  // линейная шкала аффинна, поэтому кривая по отображённым точкам совпадает с отображённой кривой
+  let mapped_curve = viewport.map_points(&pts).calc_spline(&opts).unwrap();
+  for (a, b) in mapped_curve.get_ref().iter().zip(on_canvas.get_ref()) {
+    assert!(a.approx_eq_with_precision(b, 1e-9));
+  }
+
+  let t = viewport.to_transform().unwrap();
+  for (p, expected) in curve.get_ref().iter().zip(on_canvas.get_ref()) {
+    assert!(t.apply(p).approx_eq_with_precision(expected, 1e-9));
+  }
+
+  let back = viewport.invert_points(&on_canvas);
+  for (a, b) in back.get_ref().iter().zip(curve.get_ref()) {
+    assert!(a.approx_eq_with_precision(b, 1e-9));
+  }
+
+  let log = Viewport::new(300.0, 100.0)
+    .x_domain(1.0, 1000.0)
+    .x_scale(Scale::Log)
+    .flip_y(false);
+  assert!(log
+    .map(&Point::new(10.0, 0.0))
+    .approx_eq(&Point::new(100.0, 0.0)));
+  assert!(log
+    .invert(&Point::new(200.0, 50.0))
+    .approx_eq(&Point::new(100.0, 0.5)));
+  assert!(log.map(&Point::new(-1.0, 0.0)).x.is_nan());
+  assert!(log.to_transform().is_none());
+
+  // шаг 15 секунд: домен 12:26:45 .. 12:28:00, по пикселю на секунду
+  let time = Points::from(&[(1_600_000_013_000.0, 1.0), (1_600_000_071_000.0, 2.0)]);
+  let viewport = Viewport::new(75.0, 10.0).x_scale(Scale::Time).fit(&time);
+  assert_eq!(
+    viewport.get_x_domain(),
+    (1_600_000_005_000.0, 1_600_000_080_000.0)
+  );
+  let p = viewport.map(&Point::new(1_600_000_050_000.0, 1.5));
+  assert!(p.approx_eq(&Point::new(45.0, 5.0)));
+  assert_eq!(viewport.invert(&p).x, 1_600_000_050_000.0);
+  // обратное отображение округляет до целых миллисекунд
+  assert_eq!(
+    viewport.invert(&Point::new(45.0004, 5.0)).x,
+    1_600_000_050_000.0
+  );
+  assert!(viewport.to_transform().is_some());
+
+  // две недели: шаг в двое суток, от полуночи по UTC
+  let days = Points::from(&[(1_600_000_000_000.0, 1.0), (1_601_209_600_000.0, 2.0)]);
+  let viewport = Viewport::new(100.0, 10.0).x_scale(Scale::Time).fit(&days);
+  assert_eq!(
+    viewport.get_x_domain(),
+    (1_599_955_200_000.0, 1_601_337_600_000.0)
+  );
+}
+
+#[test]
+fn clip_tst() {
+  use crate::{clip_curve, Rect};
+
+  let pts = Points::from(&data_tuples::points_arr());
+  let opts = SplineOpts::new().num_of_segments(16);
+  let segments = pts.calc_bezier(&opts).unwrap();
+  let bbox = pts.bounding_box(&opts).unwrap();
+
+  // прямоугольник, содержащий всю кривую, ничего не отрезает
+  let whole = Rect::new(
+    bbox.min_x.value - 1.0,
+    bbox.min_y.value - 1.0,
+    bbox.max_x.value + 1.0,
+    bbox.max_y.value + 1.0,
+  );
+  let runs = clip_curve(&segments, &whole);
+  assert_eq!(runs.len(), 1);
+  assert_eq!(runs[0].len(), segments.len());
+
+  let runs = pts.clip(&opts, &whole).unwrap();
+  let full = pts.calc_spline(&opts).unwrap();
+  assert_eq!(runs.len(), 1);
+  assert_eq!(runs[0].get_ref().len(), full.get_ref().len());
+  for (a, b) in runs[0].get_ref().iter().zip(full.get_ref()) {
+    assert!(a.approx_eq_with_precision(b, 1e-9));
+  }
+
+  let outside = Rect::new(-100.0, -100.0, -50.0, -50.0);
+  assert!(clip_curve(&segments, &outside).is_empty());
+  assert!(pts.clip(&opts, &outside).unwrap().is_empty());
+
+  let rect = Rect::new(
+    bbox.min_x.value + bbox.width() * 0.3,
+    bbox.min_y.value + bbox.height() * 0.3,
+    bbox.min_x.value + bbox.width() * 0.7,
+    bbox.min_y.value + bbox.height() * 0.7,
+  );
+  let on_edge = |p: &crate::Point| {
+    [
+      p.x - rect.min_x,
+      p.x - rect.max_x,
+      p.y - rect.min_y,
+      p.y - rect.max_y,
+    ]
+    .iter()
+    .any(|d| d.abs() < 1e-7)
+  };
+
+  let curves = clip_curve(&segments, &rect);
+  let runs = pts.clip(&opts, &rect).unwrap();
+  assert!(!runs.is_empty());
+  assert_eq!(curves.len(), runs.len());
+
+  for (curve, run) in curves.iter().zip(runs.iter()) {
+    let run = run.get_ref();
+    assert!(run.iter().all(|p| rect.contains(p)));
+    assert!(on_edge(&run[0]) && on_edge(run.last().unwrap()));
+    assert!(curve[0].p0.approx_eq(&run[0]));
+    assert!(curve.last().unwrap().p3.approx_eq(run.last().unwrap()));
+
+    for piece in curve {
+      for &t in &[0.0, 0.25, 0.5, 0.75, 1.0] {
+        let p = piece.point_at(t);
+        assert!(p.x >= rect.min_x - 1e-7 && p.x <= rect.max_x + 1e-7);
+        assert!(p.y >= rect.min_y - 1e-7 && p.y <= rect.max_y + 1e-7);
+      }
+    }
+    for w in curve.windows(2) {
+      assert!(w[0].p3.approx_eq(&w[1].p0));
+    }
+  }
+}
+
+#[test]
+fn simplify_tst() {
+  use crate::Point;
+
+  let dist_to_polyline = |p: &Point, line: &[Point]| {
+    line
+      .windows(2)
+      .map(|w| {
+        let (dx, dy) = (w[1].x - w[0].x, w[1].y - w[0].y);
+        let len2 = dx * dx + dy * dy;
+        let t = if len2 == 0.0 {
+          0.0
+        } else {
+          (((p.x - w[0].x) * dx + (p.y - w[0].y) * dy) / len2).clamp(0.0, 1.0)
+        };
+        (p.x - w[0].x - t * dx).hypot(p.y - w[0].y - t * dy)
+      })
+      .fold(f64::INFINITY, f64::min)
+  };
+
+  let pts = Points::from(&data_tuples::points_arr());
+  let curve = pts
+    .calc_spline(&SplineOpts::new().num_of_segments(64))
+    .unwrap();
+  let len = curve.get_ref().len();
+
+  for &tolerance in &[0.1, 1.0, 5.0] {
+    let simple = curve.simplify_rdp(tolerance);
+    let s = simple.get_ref();
+    assert!(s.len() < len);
+    assert!(s[0].approx_eq(&curve.get_ref()[0]));
+    assert!(s.last().unwrap().approx_eq(curve.get_ref().last().unwrap()));
+    for p in curve.get_ref() {
+      assert!(dist_to_polyline(p, s) <= tolerance + 1e-9);
+    }
+  }
+
+  let mut prev_len = len;
+  for &tolerance in &[0.1, 1.0, 10.0] {
+    let simple = curve.simplify_vw(tolerance);
+    let s = simple.get_ref();
+    assert!(s.len() <= prev_len);
+    prev_len = s.len();
+    assert!(s[0].approx_eq(&curve.get_ref()[0]));
+    assert!(s.last().unwrap().approx_eq(curve.get_ref().last().unwrap()));
+  }
+  assert!(prev_len < len);
+
+  // точки на прямой удаляются целиком, точки с натяжением сохраняют его
+  let mut line = Points::from(&[(0.0, 0.0), (1.0, 1.0), (2.0, 2.0), (3.0, 3.0)]);
+  line.get_mut()[3].tension = Some(0.2);
+  for simple in &[line.simplify_rdp(0.0), line.simplify_vw(1e-6)] {
+    assert_eq!(simple.get_ref().len(), 2);
+    assert_eq!(simple.get_ref()[1].tension, Some(0.2));
+  }
+
+  // треугольник площадью 1.0 сохраняется при допуске 1.0 (площадь 0.5) и удаляется при 2.0
+  let peak = Points::from(&[(0.0, 0.0), (1.0, 1.0), (2.0, 0.0)]);
+  assert_eq!(peak.simplify_vw(1.0).get_ref().len(), 3);
+  assert_eq!(peak.simplify_vw(2.0).get_ref().len(), 2);
+
+  let two = Points::from(&[(0.0, 0.0), (1.0, 1.0)]);
+  assert_eq!(two.simplify_rdp(1.0).get_ref().len(), 2);
+  assert_eq!(two.simplify_vw(1.0).get_ref().len(), 2);
+}
+
+#[test]
+fn fit_tst() {
+  use crate::FitOpts;
+
+  let opts = SplineOpts::new()
+    .tension(0.6)
+    .num_of_segments(20)
+    .hidden_point_at_start((-10.0, 5.0))
+    .hidden_point_at_end((60.0, -5.0));
+
+  // кривая по равномерным узлам восстанавливается по своим же точкам
+  let knots = Points::from(&[
+    (0.0, 1.0),
+    (10.0, 4.0),
+    (20.0, -2.0),
+    (30.0, 3.0),
+    (40.0, 0.0),
+    (50.0, 2.0),
+  ]);
+  let samples = knots.calc_spline(&opts).unwrap();
+  let fit = samples.fit(&opts, &FitOpts::new().knots(6)).unwrap();
+
+  assert!(fit.rms < 1e-9 && fit.max_residual < 1e-9);
+  for (a, b) in fit.knots.get_ref().iter().zip(knots.get_ref()) {
+    assert!(a.approx_eq(b));
+  }
+
+  // шум усредняется, сглаживание уменьшает изгиб
+  let noisy = Points::from(
+    samples
+      .get_ref()
+      .iter()
+      .enumerate()
+      .map(|(i, p)| (p.x, p.y + if i % 2 == 0 { 0.3 } else { -0.3 }))
+      .collect::<Vec<_>>(),
+  );
+  let fit = noisy.fit(&opts, &FitOpts::new().knots(6)).unwrap();
+  assert!((fit.rms - 0.3).abs() < 0.05);
+  for (a, b) in fit.knots.get_ref().iter().zip(knots.get_ref()) {
+    assert!(a.approx_eq_with_precision(b, 0.2));
+  }
+  assert_eq!(fit.residuals.len(), noisy.get_ref().len());
+
+  let bending = |pts: &Points| -> f64 {
+    pts
+      .get_ref()
+      .windows(3)
+      .map(|w| (w[0].y - 2.0 * w[1].y + w[2].y).powi(2))
+      .sum()
+  };
+  let smooth = noisy
+    .fit(&opts, &FitOpts::new().knots(6).smoothing(100.0))
+    .unwrap();
+  assert!(bending(&smooth.knots) < bending(&fit.knots));
+  assert!(smooth.rms > fit.rms);
+
+  // пропуски в данных
+  let mut with_nan = noisy.clone();
+  with_nan.get_mut()[3].y = f64::NAN;
+  let fit = with_nan.fit(&opts, &FitOpts::new().knots(6)).unwrap();
+  assert!(fit.residuals[3].is_nan() && fit.rms.is_finite());
+
+  let sparse = Points::from(&[(0.0, 0.0), (1.0, 1.0), (2.0, 0.0)]);
+  assert_eq!(
+    sparse.fit(&opts, &FitOpts::new().knots(10)).unwrap_err(),
+    Error::FitUnderdetermined
+  );
+  assert!(sparse
+    .fit(&opts, &FitOpts::new().knots(10).smoothing(1.0))
+    .is_ok());
+  assert_eq!(
+    sparse.fit(&opts, &FitOpts::new().knots(1)).unwrap_err(),
+    Error::TooFewPoints
+  );
+  let vertical = Points::from(&[(1.0, 0.0), (1.0, 1.0)]);
+  assert_eq!(
+    vertical.fit(&opts, &FitOpts::default()).unwrap_err(),
+    Error::FitUnderdetermined
+  );
+}
+
+#[test]
+fn smoothing_spline_tst() {
+  use crate::{SmoothingOpts, SmoothingSpline};
+
+  let pts = Points::from(&[
+    (0.0, 1.0),
+    (1.0, 3.0),
+    (2.5, 2.0),
+    (3.0, 4.5),
+    (5.0, 1.0),
+    (6.0, 2.0),
+    (8.0, 0.5),
+  ]);
+
+  // lambda = 0: интерполирующий сплайн класса C2
+  let s = SmoothingSpline::new(&pts, &SmoothingOpts::default()).unwrap();
+  for (a, b) in s.knots().get_ref().iter().zip(pts.get_ref()) {
+    assert!(a.approx_eq_with_precision(b, 1e-12));
+  }
+  let f = |x: f64| s.value_at(x).unwrap();
+  let e = 1e-4;
+  for p in &pts.get_ref()[1..pts.get_ref().len() - 1] {
+    let left = (f(p.x) - f(p.x - e)) / e;
+    let right = (f(p.x + e) - f(p.x)) / e;
+    assert!((left - right).abs() < 1e-2);
+
+    let left2 = (f(p.x) - 2.0 * f(p.x - e) + f(p.x - 2.0 * e)) / (e * e);
+    let right2 = (f(p.x + 2.0 * e) - 2.0 * f(p.x + e) + f(p.x)) / (e * e);
+    assert!((left2 - right2).abs() < 1e-2);
+  }
+  // естественный сплайн: нулевая кривизна на концах
+  assert_eq!(s.second_derivatives()[0], 0.0);
+  assert_eq!(*s.second_derivatives().last().unwrap(), 0.0);
+
+  // большая lambda даёт прямую наименьших квадратов
+  let line = SmoothingSpline::new(&pts, &SmoothingOpts::new().lambda(1e9)).unwrap();
+  let ps = pts.get_ref();
+  let n = ps.len() as f64;
+  let (sx, sy) = ps
+    .iter()
+    .fold((0.0, 0.0), |(sx, sy), p| (sx + p.x, sy + p.y));
+  let (mx, my) = (sx / n, sy / n);
+  let slope = ps.iter().map(|p| (p.x - mx) * (p.y - my)).sum::<f64>()
+    / ps.iter().map(|p| (p.x - mx).powi(2)).sum::<f64>();
+  for k in line.knots().get_ref() {
+    assert!((k.y - (my + slope * (k.x - mx))).abs() < 1e-4);
+  }
+
+  // больший вес притягивает кривую к точке
+  let mut weights = vec![1.0; ps.len()];
+  let opts = SmoothingOpts::new().lambda(1.0);
+  let plain = SmoothingSpline::new(&pts, &opts).unwrap();
+  weights[3] = 100.0;
+  let weighted = SmoothingSpline::new(&pts, &opts.clone().weights(weights)).unwrap();
+  let dist = |s: &SmoothingSpline| (s.knots().get_ref()[3].y - ps[3].y).abs();
+  assert!(dist(&weighted) < dist(&plain) / 10.0);
+
+  let curve = pts
+    .calc_smoothing_spline(&SplineOpts::new().num_of_segments(5), &opts)
+    .unwrap();
+  assert_eq!(curve.get_ref().len(), (ps.len() - 1) * 5 + 1);
+  assert!(curve.get_ref()[5].approx_eq(&plain.knots().get_ref()[1]));
+
+  let unsorted = Points::from(&[(0.0, 0.0), (2.0, 1.0), (1.0, 0.0)]);
+  assert_eq!(
+    SmoothingSpline::new(&unsorted, &opts).unwrap_err(),
+    Error::NotIncreasingX
+  );
+  assert_eq!(
+    SmoothingSpline::new(&pts, &opts.clone().weights(vec![1.0; 3])).unwrap_err(),
+    Error::InvalidWeights
+  );
+  assert_eq!(
+    SmoothingSpline::new(&pts, &opts.weights(vec![0.0; 7])).unwrap_err(),
+    Error::InvalidWeights
+  );
+  assert_eq!(
+    SmoothingSpline::new(&pts, &SmoothingOpts::new().lambda(f64::INFINITY)).unwrap_err(),
+    Error::SmoothingSingular
+  );
+}
+
+#[test]
+fn fit_bezier_tst() {
+  use crate::{closest_point, Point, PolySegment};
+
+  // штрих: дуга, излом, волна, с небольшим шумом
+  let mut samples: Vec<(f64, f64)> = (0..=60)
+    .map(|i| {
+      let a = std::f64::consts::PI * i as f64 / 60.0;
+      (100.0 - 100.0 * a.cos(), 100.0 * a.sin())
+    })
+    .collect();
+  samples.extend((1..=80).map(|i| {
+    let x = 200.0 + i as f64 * 2.5;
+    (x, 40.0 * (i as f64 / 12.0).sin())
+  }));
+  for (i, p) in samples.iter_mut().enumerate() {
+    p.1 += if i % 3 == 0 { 0.2 } else { -0.1 };
+  }
+  let stroke = Points::from(&samples);
+  let corner = Point::new(200.0, samples[60].1);
+
+  let tolerance = 1.0;
+  let check = |segments: &[PolySegment]| {
+    for p in stroke.get_ref() {
+      let proj = closest_point(segments, p).unwrap();
+      assert!(
+        proj.distance <= tolerance + 1e-6,
+        "{:?} {}",
+        p,
+        proj.distance
+      );
+    }
+  };
+
+  let segments = stroke.fit_bezier(tolerance);
+  assert!(segments.len() < 20);
+  assert!(segments[0].p0.approx_eq(&stroke.get_ref()[0]));
+  assert!(segments
+    .last()
+    .unwrap()
+    .p3
+    .approx_eq(stroke.get_ref().last().unwrap()));
+  for w in segments.windows(2) {
+    assert!(w[0].p3.approx_eq(&w[1].p0));
+  }
+  check(&segments.iter().map(PolySegment::from).collect::<Vec<_>>());
+
+  // излом сохраняется: касательные по разные стороны смотрят в разные стороны
+  let at_corner = segments
+    .iter()
+    .position(|s| s.p3.approx_eq(&corner))
+    .unwrap();
+  let (d1, d2) = (
+    segments[at_corner].derivative_at(1.0),
+    segments[at_corner + 1].derivative_at(0.0),
+  );
+  let cos = (d1.x * d2.x + d1.y * d2.y) / (d1.x.hypot(d1.y) * d2.x.hypot(d2.y));
+  assert!(cos < 0.5);
+
+  let knots = stroke.fit_knots(tolerance);
+  let k = knots.get_ref();
+  assert!(k.len() < stroke.get_ref().len() / 3);
+  assert!(k
+    .windows(2)
+    .any(|w| w[0].approx_eq(&corner) && w[1].approx_eq(&corner) && w[0].tension == Some(0.0)));
+  check(&knots.calc_poly(&SplineOpts::default()).unwrap());
+
+  assert!(Points::from(Vec::<Point>::new()).fit_bezier(1.0).is_empty());
+  assert!(Points::from(&[(1.0, 1.0), (1.0, 1.0)])
+    .fit_bezier(1.0)
+    .is_empty());
+  let line = Points::from(&[(0.0, 0.0), (10.0, 0.0)]);
+  assert_eq!(line.fit_bezier(1.0).len(), 1);
+  assert_eq!(line.fit_knots(1.0).get_ref().len(), 2);
+}
+
+#[test]
+fn resample_tst() {
+  use crate::{Extrapolation, Grid, ResampleOpts, SmoothingOpts, SmoothingSpline, SplineMode};
+
+  let pts = Points::from(&[
+    (0.0, 1.0),
+    (1.5, 3.0),
+    (2.0, 2.0),
+    (4.0, 4.5),
+    (7.0, 1.0),
+    (8.0, 2.0),
+  ]);
+  let spline_opts = SplineOpts::new().tension(0.4).num_of_segments(8);
+  let opts = ResampleOpts::new().mode(SplineMode::Cardinal(spline_opts.clone()));
+
+  // значения в узлах и в рассчитанных точках кривой
+  let xs: Vec<f64> = pts.get_ref().iter().map(|p| p.x).collect();
+  let ys = pts.resample(&Grid::explicit(xs), &opts).unwrap();
+  for (y, p) in ys.iter().zip(pts.get_ref()) {
+    assert!((y - p.y).abs() < 1e-9);
+  }
+  let curve = pts.calc_spline(&spline_opts).unwrap();
+  let grid = Grid::explicit(curve.get_ref().iter().map(|p| p.x));
+  let ys = pts.resample(&grid, &opts).unwrap();
+  for (y, p) in ys.iter().zip(curve.get_ref()) {
+    assert!((y - p.y).abs() < 1e-6);
+  }
+
+  // та же кривая как функция y(x)
+  let f = pts.spline_function(&spline_opts).unwrap();
+  assert_eq!(f.range(), (0.0, 8.0));
+  assert!((f.value_at(4.0).unwrap() - 4.5).abs() < 1e-9);
+  assert_eq!(f.value_at(9.0), None);
+
+  let grid = Grid::regular(-1.0, 0.5, 21);
+  assert_eq!(grid.xs().len(), 21);
+  assert_eq!(grid.xs()[20], 9.0);
+  assert_eq!(pts.resample(&grid, &opts).unwrap_err(), Error::OutOfRange);
+
+  let nan = pts
+    .resample(&grid, &opts.clone().extrapolation(Extrapolation::NaN))
+    .unwrap();
+  assert!(nan[0].is_nan() && nan[1].is_nan() && !nan[2].is_nan() && nan[20].is_nan());
+
+  let clamp = pts
+    .resample(&grid, &opts.clone().extrapolation(Extrapolation::Constant))
+    .unwrap();
+  assert!((clamp[0] - 1.0).abs() < 1e-9 && (clamp[20] - 2.0).abs() < 1e-9);
+
+  let linear = pts
+    .resample(&grid, &opts.clone().extrapolation(Extrapolation::Linear))
+    .unwrap();
+  // продолжение по касательной: конечные разности совпадают с наклоном у края
+  let inside = pts
+    .resample(&Grid::explicit(vec![0.0, 1e-6, 8.0 - 1e-6, 8.0]), &opts)
+    .unwrap();
+  let (s0, s1) = (
+    (inside[1] - inside[0]) / 1e-6,
+    (inside[3] - inside[2]) / 1e-6,
+  );
+  assert!((linear[0] - (1.0 - s0)).abs() < 1e-4);
+  assert!((linear[20] - (2.0 + s1)).abs() < 1e-4);
+  assert!((linear[10] - clamp[10]).abs() < 1e-12);
+
+  // сглаживающий сплайн
+  let smoothing = SmoothingOpts::new().lambda(0.5);
+  let s = SmoothingSpline::new(&pts, &smoothing).unwrap();
+  let opts = ResampleOpts::new()
+    .mode(SplineMode::Smoothing(smoothing))
+    .extrapolation(Extrapolation::Linear);
+  let ys = pts.resample(&Grid::regular(0.0, 0.25, 33), &opts).unwrap();
+  for (i, y) in ys.iter().enumerate() {
+    assert!((y - s.value_at(i as f64 * 0.25).unwrap()).abs() < 1e-12);
+  }
+  let out = pts.resample(&Grid::explicit(vec![10.0]), &opts).unwrap();
+  let slope = s.derivative_at(8.0).unwrap();
+  assert!((out[0] - (s.value_at(8.0).unwrap() + 2.0 * slope)).abs() < 1e-9);
+  // естественный сплайн продолжается кубически так же, как линейно
+  let cubic = opts.clone().extrapolation(Extrapolation::Cubic);
+  assert_eq!(
+    pts.resample(&Grid::explicit(vec![10.0]), &cubic).unwrap(),
+    out
+  );
+  let constant = opts.extrapolation(Extrapolation::Constant);
+  let out = pts
+    .resample(&Grid::explicit(vec![-1.0]), &constant)
+    .unwrap();
+  assert_eq!(out[0], s.value_at(0.0).unwrap());
+
+  let unsorted = Points::from(&[(0.0, 0.0), (2.0, 1.0), (1.0, 0.0)]);
+  assert_eq!(
+    unsorted
+      .resample(&Grid::regular(0.0, 1.0, 2), &ResampleOpts::default())
+      .unwrap_err(),
+    Error::NotIncreasingX
+  );
+}
+
+#[test]
+fn extrapolation_tst() {
+  use crate::{Extrapolation, Grid, ResampleOpts, SplineMode};
+
+  let pts = Points::from(&[(0.0, 1.0), (1.0, 3.0), (3.0, 2.0), (4.0, 4.0)]);
+  let opts = SplineOpts::new().tension(0.4).num_of_segments(8);
+  let value_at = |o: &SplineOpts, x: f64| pts.spline_function(o).unwrap().value_at(x);
+
+  // по умолчанию за краями значений нет, внутри значения совпадают с точками
+  let f = pts.spline_function(&opts).unwrap();
+  assert_eq!(f.range(), (0.0, 4.0));
+  assert_eq!(f.value_at(5.0), None);
+  assert!((f.value_at(3.0).unwrap() - 2.0).abs() < 1e-9);
+  assert_eq!(
+    pts.calc_spline_extended(&opts, -1.0, 4.0).unwrap_err(),
+    Error::OutOfRange
+  );
+  // без выхода за края результат как у calc_spline
+  let plain = pts.calc_spline(&opts).unwrap();
+  let same = pts.calc_spline_extended(&opts, 0.0, 4.0).unwrap();
+  assert_eq!(plain.get_ref().len(), same.get_ref().len());
+  for (a, b) in plain.get_ref().iter().zip(same.get_ref()) {
+    assert!(a.approx_eq(b));
+  }
+
+  let constant = opts.clone().extrapolation(Extrapolation::Constant);
+  assert!((value_at(&constant, -3.0).unwrap() - 1.0).abs() < 1e-9);
+  assert!((value_at(&constant, 10.0).unwrap() - 4.0).abs() < 1e-9);
+
+  let linear = opts.clone().extrapolation(Extrapolation::Linear);
+  let h = 1e-6;
+  let s1 = (value_at(&opts, 4.0).unwrap() - value_at(&opts, 4.0 - h).unwrap()) / h;
+  assert!((value_at(&linear, 6.0).unwrap() - (4.0 + 2.0 * s1)).abs() < 1e-4);
+
+  // кубическое продолжение гладко переходит через край
+  let cubic = opts.clone().extrapolation(Extrapolation::Cubic);
+  let after = value_at(&cubic, 4.0 + h).unwrap();
+  assert!(((after - 4.0) / h - s1).abs() < 1e-3);
+
+  for o in &[&constant, &linear, &cubic] {
+    let curve = pts.calc_spline_extended(o, -2.0, 6.0).unwrap();
+    let curve = curve.get_ref();
+    assert!(curve[0].approx_eq(&(-2.0, value_at(o, -2.0).unwrap()).into()));
+    assert!(curve[curve.len() - 1].approx_eq(&(6.0, value_at(o, 6.0).unwrap()).into()));
+    assert!(curve.windows(2).all(|w| w[1].x > w[0].x));
+    for p in curve {
+      assert!((p.y - value_at(o, p.x).unwrap()).abs() < 1e-6);
+    }
+  }
+
+  // прямое продолжение из одной точки, кубическое ограничено по числу точек
+  for o in &[&constant, &linear] {
+    let curve = pts.calc_spline_extended(o, -1e7, 1e7).unwrap();
+    assert_eq!(curve.get_ref().len(), plain.get_ref().len() + 2);
+  }
+  let far = pts.calc_spline_extended(&cubic, 0.0, 1e7).unwrap();
+  assert_eq!(far.get_ref().len(), plain.get_ref().len() + 1000);
+  // с NaN кривая не продолжается
+  let nan = opts.clone().extrapolation(Extrapolation::NaN);
+  assert!(value_at(&nan, 5.0).unwrap().is_nan());
+  let curve = pts.calc_spline_extended(&nan, -2.0, 6.0).unwrap();
+  assert_eq!(curve.get_ref().len(), plain.get_ref().len());
+  for &(from, to) in &[
+    (f64::NEG_INFINITY, 4.0),
+    (0.0, f64::INFINITY),
+    (f64::NAN, 4.0),
+  ] {
+    assert_eq!(
+      pts.calc_spline_extended(&linear, from, to).unwrap_err(),
+      Error::InvalidRange
+    );
+  }
+
+  // resample использует своё правило вместо правила из опций кривой
+  let grid = Grid::explicit(vec![-2.0, 2.0, 6.0]);
+  let ys = pts
+    .resample(
+      &grid,
+      &ResampleOpts::new()
+        .mode(SplineMode::Cardinal(opts.clone()))
+        .extrapolation(Extrapolation::Cubic),
+    )
+    .unwrap();
+  for (y, x) in ys.iter().zip(grid.xs()) {
+    assert!((y - value_at(&cubic, x).unwrap()).abs() < 1e-12);
+  }
+
+  let unsorted = Points::from(&[(0.0, 0.0), (2.0, 1.0), (1.0, 0.0)]);
+  assert_eq!(
+    unsorted.spline_function(&linear).unwrap_err(),
+    Error::NotIncreasingX
+  );
+}
+
+#[test]
+fn gaps_tst() {
+  use crate::GapOpts;
+
+  let nan = f64::NAN;
+  let pts = Points::from(&[
+    (nan, nan),
+    (0.0, 1.0),
+    (1.0, 2.0),
+    (2.0, 0.0),
+    (3.0, nan),
+    (4.0, 1.0),
+    (5.0, 3.0),
+    (20.0, 2.0),
+    (21.0, 4.0),
+    (f64::INFINITY, 0.0),
+  ]);
+
+  let runs = pts.split_at_gaps(&GapOpts::default());
+  let lens: Vec<usize> = runs.iter().map(|r| r.get_ref().len()).collect();
+  assert_eq!(lens, vec![3, 4]);
+
+  let gap_opts = GapOpts::new().max_gap(5.0);
+  let runs = pts.split_at_gaps(&gap_opts);
+  let lens: Vec<usize> = runs.iter().map(|r| r.get_ref().len()).collect();
+  assert_eq!(lens, vec![3, 2, 2]);
+  assert!(runs[2].get_ref()[0].approx_eq(&(20.0, 2.0).into()));
+
+  // каждый кусок считается отдельно, скрытые точки только у краёв всего ряда
+  let opts = SplineOpts::new()
+    .num_of_segments(4)
+    .hidden_point_at_start((-1.0, 5.0))
+    .hidden_point_at_end((22.0, -5.0));
+  let plain = SplineOpts::new().num_of_segments(4);
+  let first = runs[0]
+    .calc_spline(&plain.clone().hidden_point_at_start((-1.0, 5.0)))
+    .unwrap();
+  let middle = runs[1].calc_spline(&plain).unwrap();
+  let last = runs[2]
+    .calc_spline(&plain.clone().hidden_point_at_end((22.0, -5.0)))
+    .unwrap();
+  let assert_curves = |curves: &[Points], expected: &[&Points]| {
+    assert_eq!(curves.len(), expected.len());
+    for (curve, expected) in curves.iter().zip(expected) {
+      assert_eq!(curve.get_ref().len(), expected.get_ref().len());
+      for (a, b) in curve.get_ref().iter().zip(expected.get_ref()) {
+        assert!(a.approx_eq(b));
+      }
+    }
+  };
+
+  let inner = Points::from(pts.get_ref()[1..pts.get_ref().len() - 1].to_vec());
+  let curves = inner.calc_spline_with_gaps(&opts, &gap_opts).unwrap();
+  assert_curves(&curves, &[&first, &middle, &last]);
+
+  // ряд начинается и заканчивается разрывом, поэтому скрытые точки не используются
+  let first = runs[0].calc_spline(&plain).unwrap();
+  let last = runs[2].calc_spline(&plain).unwrap();
+  let curves = pts.calc_spline_with_gaps(&opts, &gap_opts).unwrap();
+  assert_curves(&curves, &[&first, &middle, &last]);
+
+  // одиночная точка остаётся как есть, пустой ввод даёт пустой результат
+  let single = Points::from(&[(0.0, 1.0), (nan, nan), (2.0, 2.0), (3.0, 1.0)]);
+  let curves = single
+    .calc_spline_with_gaps(&SplineOpts::default(), &GapOpts::default())
+    .unwrap();
+  assert_eq!(curves[0].get_ref().len(), 1);
+  let empty = Points::from(&[(nan, nan)]);
+  assert!(empty
+    .calc_spline_with_gaps(&SplineOpts::default(), &GapOpts::default())
+    .unwrap()
+    .is_empty());
+}
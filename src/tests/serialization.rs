@@ -0,0 +1,62 @@
+use crate::{Error, Point, Points, SplineOpts};
+
+#[test]
+fn point_de() {
+  let from_arr: Point = serde_json::from_str("[1.5, 2.0]").unwrap();
+  let from_obj: Point = serde_json::from_str(r#"{"x": 1.5, "y": 2.0}"#).unwrap();
+  let with_tension: Point =
+    serde_json::from_str(r#"{"x": 1.5, "y": 2.0, "tension": 0.3}"#).unwrap();
+
+  assert!(from_arr.approx_eq(&Point::new(1.5, 2.0)));
+  assert!(from_obj.approx_eq(&from_arr));
+  assert_eq!(from_obj.tension, None);
+  assert_eq!(with_tension.tension, Some(0.3));
+
+  assert!(serde_json::from_str::<Point>("[1.5]").is_err());
+}
+
+#[test]
+fn points_de() {
+  let list: Points = serde_json::from_str(r#"[[1.0, 2.0], {"x": 3.0, "y": 4.0}]"#).unwrap();
+  let flatten: Points = serde_json::from_str("[1.0, 2.0, 3.0, 4.0]").unwrap();
+
+  assert_eq!(
+    Into::<Vec<(f64, f64)>>::into(list),
+    Into::<Vec<(f64, f64)>>::into(flatten)
+  );
+
+  let one = serde_json::from_str::<Points>("[[1.0, 2.0]]").unwrap_err();
+  assert_eq!(one.to_string(), Error::TooFewPoints.msg());
+
+  let missing_y = serde_json::from_str::<Points>("[1.0, 2.0, 3.0]").unwrap_err();
+  assert_eq!(missing_y.to_string(), Error::MissingY.msg());
+}
+
+#[test]
+fn roundtrip() {
+  let mut points = Points::from(&[(1.0, 2.0), (3.0, 4.0)]);
+  points.get_mut()[1].tension = Some(0.7);
+
+  let json = serde_json::to_string(&points).unwrap();
+  assert_eq!(
+    json,
+    r#"[{"x":1.0,"y":2.0},{"x":3.0,"y":4.0,"tension":0.7}]"#
+  );
+
+  let back: Points = serde_json::from_str(&json).unwrap();
+  assert_eq!(back.get_ref()[1].tension, Some(0.7));
+
+  let opts = SplineOpts::new()
+    .num_of_segments(5)
+    .hidden_point_at_end((1.0, 1.0))
+    .extrapolation(crate::Extrapolation::Linear);
+  let opts_json = serde_json::to_string(&opts).unwrap();
+  let opts_back: SplineOpts = serde_json::from_str(&opts_json).unwrap();
+  assert_eq!(opts_back.get_num_of_segments(), 5);
+  assert!(opts_back.get_hidden_point_at_end().is_some());
+  assert_eq!(opts_back.get_extrapolation(), crate::Extrapolation::Linear);
+
+  let partial: SplineOpts = serde_json::from_str(r#"{"tension": 0.1}"#).unwrap();
+  assert_eq!(partial.get_num_of_segments(), crate::DEFAULT_SEGMENTS);
+  assert!(partial.get_hidden_point_at_start().is_none());
+}
@@ -0,0 +1,236 @@
+use crate::{BezierSegment, Point, Points, PolySegment};
+
+///
+/// 2D affine transform, the same as used in SVG and canvas:
+///
+/// ```text
+/// x' = a * x + c * y + e
+/// y' = b * x + d * y + f
+/// ```
+///
+/// The curve is affine invariant: transforming source points (and hidden points from options)
+/// and then calculating the curve gives the same result as transforming the calculated curve.
+///
+/// # Example
+/// ```
+/// use cubic_spline::{Point, Points, SplineOpts, Transform};
+///
+/// let t = Transform::scale(2.0, -1.0).then_translate(10.0, 100.0);
+///
+/// let mut p = Point::new(3.0, 4.0);
+/// p.transform(&t);
+/// assert!(p.approx_eq(&Point::new(16.0, 96.0)));
+///
+/// let inv = t.inverse().unwrap();
+/// p.transform(&inv);
+/// assert!(p.approx_eq(&Point::new(3.0, 4.0)));
+///
+/// let mut pts = Points::from(&[(0.0, 0.0), (1.0, 2.0), (2.0, 0.0)]);
+/// let opts = SplineOpts::default();
+/// let mut curve = pts.calc_spline(&opts).unwrap();
+///
+/// curve.transform(&t);
+/// pts.transform(&t);
+/// let curve_of_transformed = pts.calc_spline(&opts.transformed(&t)).unwrap();
+///
+/// assert!(curve.get_ref()[5].approx_eq(&curve_of_transformed.get_ref()[5]));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Transform {
+  ///
+  /// Scale in x-axis.
+  pub a: f64,
+
+  ///
+  /// Skew in y-axis.
+  pub b: f64,
+
+  ///
+  /// Skew in x-axis.
+  pub c: f64,
+
+  ///
+  /// Scale in y-axis.
+  pub d: f64,
+
+  ///
+  /// Translation in x-axis.
+  pub e: f64,
+
+  ///
+  /// Translation in y-axis.
+  pub f: f64,
+}
+
+impl Transform {
+  ///
+  /// Creates new transform from the matrix values.
+  pub fn new(a: f64, b: f64, c: f64, d: f64, e: f64, f: f64) -> Self {
+    Transform { a, b, c, d, e, f }
+  }
+
+  ///
+  /// Transform that changes nothing.
+  pub fn identity() -> Self {
+    Transform::new(1.0, 0.0, 0.0, 1.0, 0.0, 0.0)
+  }
+
+  ///
+  /// Moves points by `tx` and `ty`.
+  pub fn translate(tx: f64, ty: f64) -> Self {
+    Transform::new(1.0, 0.0, 0.0, 1.0, tx, ty)
+  }
+
+  ///
+  /// Scales points relative to the origin.
+  pub fn scale(sx: f64, sy: f64) -> Self {
+    Transform::new(sx, 0.0, 0.0, sy, 0.0, 0.0)
+  }
+
+  ///
+  /// Rotates points around the origin by `angle` in radians
+  /// (counterclockwise in the coordinate system with y-axis up).
+  pub fn rotate(angle: f64) -> Self {
+    let (sin, cos) = angle.sin_cos();
+    Transform::new(cos, sin, -sin, cos, 0.0, 0.0)
+  }
+
+  ///
+  /// Skews points by angles in radians along x-axis and y-axis.
+  pub fn skew(ax: f64, ay: f64) -> Self {
+    Transform::new(1.0, ay.tan(), ax.tan(), 1.0, 0.0, 0.0)
+  }
+
+  ///
+  /// Returns the transform that applies `self` first and then `next`.
+  ///
+  /// ```
+  /// use cubic_spline::{Point, Transform};
+  ///
+  /// let t = Transform::translate(1.0, 0.0).then(&Transform::scale(2.0, 2.0));
+  ///
+  /// assert!(t.apply(&Point::new(1.0, 1.0)).approx_eq(&Point::new(4.0, 2.0)));
+  /// ```
+  pub fn then(&self, next: &Transform) -> Self {
+    Transform::new(
+      next.a * self.a + next.c * self.b,
+      next.b * self.a + next.d * self.b,
+      next.a * self.c + next.c * self.d,
+      next.b * self.c + next.d * self.d,
+      next.a * self.e + next.c * self.f + next.e,
+      next.b * self.e + next.d * self.f + next.f,
+    )
+  }
+
+  ///
+  /// The same as `self.then(&Transform::translate(tx, ty))`.
+  pub fn then_translate(&self, tx: f64, ty: f64) -> Self {
+    self.then(&Transform::translate(tx, ty))
+  }
+
+  ///
+  /// The same as `self.then(&Transform::scale(sx, sy))`.
+  pub fn then_scale(&self, sx: f64, sy: f64) -> Self {
+    self.then(&Transform::scale(sx, sy))
+  }
+
+  ///
+  /// The same as `self.then(&Transform::rotate(angle))`.
+  pub fn then_rotate(&self, angle: f64) -> Self {
+    self.then(&Transform::rotate(angle))
+  }
+
+  ///
+  /// The same as `self.then(&Transform::skew(ax, ay))`.
+  pub fn then_skew(&self, ax: f64, ay: f64) -> Self {
+    self.then(&Transform::skew(ax, ay))
+  }
+
+  ///
+  /// Returns the transform that undoes this one
+  /// or `None` if it collapses points into a line or a point.
+  pub fn inverse(&self) -> Option<Self> {
+    let det = self.a * self.d - self.b * self.c;
+    if det == 0.0 || !det.is_finite() {
+      return None;
+    }
+
+    let a = self.d / det;
+    let b = -self.b / det;
+    let c = -self.c / det;
+    let d = self.a / det;
+    Some(Transform::new(
+      a,
+      b,
+      c,
+      d,
+      -(a * self.e + c * self.f),
+      -(b * self.e + d * self.f),
+    ))
+  }
+
+  ///
+  /// Returns transformed point. Tension of the point is kept.
+  pub fn apply(&self, p: &Point) -> Point {
+    Point {
+      x: self.a * p.x + self.c * p.y + self.e,
+      y: self.b * p.x + self.d * p.y + self.f,
+      tension: p.tension,
+    }
+  }
+
+  // Для векторов (касательных, коэффициентов при степенях `t`) сдвиг не применяется
+  fn apply_linear(&self, x: f64, y: f64) -> (f64, f64) {
+    (self.a * x + self.c * y, self.b * x + self.d * y)
+  }
+}
+
+impl Default for Transform {
+  fn default() -> Self {
+    Transform::identity()
+  }
+}
+
+impl Point {
+  ///
+  /// Applies affine transform to the point.
+  pub fn transform(&mut self, t: &Transform) {
+    *self = t.apply(self);
+  }
+}
+
+impl Points {
+  ///
+  /// Applies affine transform to all points.
+  /// Works the same way for source points and for calculated points of the curve.
+  pub fn transform(&mut self, t: &Transform) {
+    self.get_mut().iter_mut().for_each(|p| p.transform(t));
+  }
+}
+
+impl BezierSegment {
+  ///
+  /// Applies affine transform to the segment. The result is exactly the transformed curve.
+  pub fn transform(&mut self, t: &Transform) {
+    self.p0.transform(t);
+    self.c1.transform(t);
+    self.c2.transform(t);
+    self.p3.transform(t);
+  }
+}
+
+impl PolySegment {
+  ///
+  /// Applies affine transform to the segment. The result is exactly the transformed curve.
+  pub fn transform(&mut self, t: &Transform) {
+    let start = t.apply(&Point::new(self.x[0], self.y[0]));
+    self.x[0] = start.x;
+    self.y[0] = start.y;
+
+    for i in 1..4 {
+      let (x, y) = t.apply_linear(self.x[i], self.y[i]);
+      self.x[i] = x;
+      self.y[i] = y;
+    }
+  }
+}
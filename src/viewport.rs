@@ -0,0 +1,428 @@
+use crate::{Point, Points, Transform};
+
+///
+/// How values of the data domain are spread over the canvas.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Scale {
+  ///
+  /// Equal distances in the data are equal distances on the canvas.
+  Linear,
+
+  ///
+  /// Equal ratios in the data are equal distances on the canvas (base 10).
+  /// The domain and the values should be positive, other values are mapped to `NaN`.
+  Log,
+
+  ///
+  /// Linear scale for timestamps in milliseconds since the Unix epoch (UTC).
+  /// [`fit`] extends the domain to whole time steps (seconds, minutes, hours, days, ...),
+  /// and inverted values are rounded to whole milliseconds.
+  ///
+  /// [`fit`]: struct.Viewport.html#method.fit
+  Time,
+}
+
+// Вручную, а не через `#[default]`, которому нужен Rust 1.62
+#[allow(clippy::derivable_impls)]
+impl Default for Scale {
+  fn default() -> Self {
+    Scale::Linear
+  }
+}
+
+impl Scale {
+  // Переводит значение в пространство, где шкала линейная
+  fn forward(self, v: f64) -> f64 {
+    match self {
+      Scale::Log => {
+        if v > 0.0 {
+          v.log10()
+        } else {
+          f64::NAN
+        }
+      }
+      Scale::Linear | Scale::Time => v,
+    }
+  }
+
+  fn backward(self, v: f64) -> f64 {
+    match self {
+      Scale::Log => 10f64.powf(v),
+      Scale::Time => v.round(),
+      Scale::Linear => v,
+    }
+  }
+
+  // Доля `0..1` положения `v` внутри `domain`
+  fn ratio_of(self, v: f64, (min, max): (f64, f64)) -> f64 {
+    let (min, max) = (self.forward(min), self.forward(max));
+    let span = max - min;
+    if span == 0.0 {
+      return 0.5;
+    }
+    (self.forward(v) - min) / span
+  }
+
+  fn value_at(self, r: f64, (min, max): (f64, f64)) -> f64 {
+    let (min, max) = (self.forward(min), self.forward(max));
+    self.backward(min + r * (max - min))
+  }
+}
+
+///
+/// Maps points from the data domain to the canvas and back.
+///
+/// The canvas has its origin in the top left corner and y-axis going down,
+/// so by default y-values are flipped with [`Point::invert_vertically`].
+/// `padding` is kept free on each side of the canvas.
+///
+/// Linear and time scales are affine, so for them it doesn't matter whether you map
+/// the source points or the calculated curve (see [`to_transform`]).
+/// With logarithmic scale map the source points and calculate the curve on the canvas.
+///
+/// # Example
+/// ```
+/// use cubic_spline::{Point, Points, Viewport};
+///
+/// let pts = Points::from(&[(0.0, 0.0), (5.0, 10.0), (10.0, 5.0)]);
+/// let viewport = Viewport::new(120.0, 70.0).padding(10.0).fit(&pts);
+///
+/// let on_canvas = viewport.map_points(&pts);
+/// assert!(on_canvas.get_ref()[0].approx_eq(&Point::new(10.0, 60.0)));
+/// assert!(on_canvas.get_ref()[1].approx_eq(&Point::new(60.0, 10.0)));
+///
+/// let clicked = viewport.invert(&Point::new(110.0, 35.0));
+/// assert!(clicked.approx_eq(&Point::new(10.0, 5.0)));
+/// ```
+///
+/// [`Point::invert_vertically`]: struct.Point.html#method.invert_vertically
+/// [`to_transform`]: struct.Viewport.html#method.to_transform
+#[derive(Clone, Debug, PartialEq)]
+pub struct Viewport {
+  width: f64,
+  height: f64,
+  padding: f64,
+  x_domain: (f64, f64),
+  y_domain: (f64, f64),
+  x_scale: Scale,
+  y_scale: Scale,
+  flip_y: bool,
+}
+
+impl Viewport {
+  ///
+  /// Creates new viewport for the canvas of given size.
+  /// Domains are `0.0..1.0` until set or [`fit`].
+  ///
+  /// [`fit`]: struct.Viewport.html#method.fit
+  pub fn new(width: f64, height: f64) -> Self {
+    Viewport {
+      width,
+      height,
+      padding: 0.0,
+      x_domain: (0.0, 1.0),
+      y_domain: (0.0, 1.0),
+      x_scale: Scale::Linear,
+      y_scale: Scale::Linear,
+      flip_y: true,
+    }
+  }
+
+  ///
+  /// Sets the space kept free on each side of the canvas.
+  pub fn padding(mut self, padding: f64) -> Self {
+    self.padding = padding;
+    self
+  }
+
+  ///
+  /// Sets the range of x-values that fills the canvas.
+  pub fn x_domain(mut self, min: f64, max: f64) -> Self {
+    self.x_domain = (min, max);
+    self
+  }
+
+  ///
+  /// Sets the range of y-values that fills the canvas.
+  pub fn y_domain(mut self, min: f64, max: f64) -> Self {
+    self.y_domain = (min, max);
+    self
+  }
+
+  ///
+  /// Sets the scale of x-axis.
+  pub fn x_scale(mut self, scale: Scale) -> Self {
+    self.x_scale = scale;
+    self
+  }
+
+  ///
+  /// Sets the scale of y-axis.
+  pub fn y_scale(mut self, scale: Scale) -> Self {
+    self.y_scale = scale;
+    self
+  }
+
+  ///
+  /// Sets whether y-values go up (`true`, default) or down on the canvas.
+  pub fn flip_y(mut self, flip: bool) -> Self {
+    self.flip_y = flip;
+    self
+  }
+
+  ///
+  /// Sets both domains to the range of passed points.
+  /// The curve may bend beyond the source points,
+  /// so pass the calculated points to keep the whole curve on the canvas.
+  ///
+  /// Points with non-finite coordinates, and non-positive values for logarithmic scale, are skipped.
+  /// If no point is left, domains stay the same.
+  ///
+  /// Domain of time scale is extended to whole time steps, so that it has at most 10 of them:
+  ///
+  /// ```
+  /// use cubic_spline::{Points, Scale, Viewport};
+  ///
+  /// // 2020-09-13T12:26:53Z .. 2020-09-13T12:27:51Z
+  /// let pts = Points::from(&[(1_600_000_013_000.0, 1.0), (1_600_000_071_000.0, 2.0)]);
+  /// let viewport = Viewport::new(75.0, 10.0).x_scale(Scale::Time).fit(&pts);
+  ///
+  /// // 12:26:45 .. 12:28:00, with steps of 15 seconds
+  /// assert_eq!(viewport.get_x_domain(), (1_600_000_005_000.0, 1_600_000_080_000.0));
+  /// ```
+  pub fn fit(mut self, points: &Points) -> Self {
+    if let Some(d) = domain_of(points.get_ref().iter().map(|p| p.x), self.x_scale) {
+      self.x_domain = d;
+    }
+    if let Some(d) = domain_of(points.get_ref().iter().map(|p| p.y), self.y_scale) {
+      self.y_domain = d;
+    }
+    self
+  }
+
+  //
+  // Gets width.
+  pub fn get_width(&self) -> f64 {
+    self.width
+  }
+
+  //
+  // Gets height.
+  pub fn get_height(&self) -> f64 {
+    self.height
+  }
+
+  //
+  // Gets padding.
+  pub fn get_padding(&self) -> f64 {
+    self.padding
+  }
+
+  //
+  // Gets x_domain.
+  pub fn get_x_domain(&self) -> (f64, f64) {
+    self.x_domain
+  }
+
+  //
+  // Gets y_domain.
+  pub fn get_y_domain(&self) -> (f64, f64) {
+    self.y_domain
+  }
+
+  //
+  // Gets x_scale.
+  pub fn get_x_scale(&self) -> Scale {
+    self.x_scale
+  }
+
+  //
+  // Gets y_scale.
+  pub fn get_y_scale(&self) -> Scale {
+    self.y_scale
+  }
+
+  ///
+  /// Maps the point from the data domain to the canvas. Tension of the point is kept.
+  pub fn map(&self, p: &Point) -> Point {
+    let rx = self.x_scale.ratio_of(p.x, self.x_domain);
+    let ry = self.y_scale.ratio_of(p.y, self.y_domain);
+
+    let mut result = Point {
+      x: self.padding + rx * self.inner_width(),
+      y: self.padding + ry * self.inner_height(),
+      tension: p.tension,
+    };
+    if self.flip_y {
+      result.invert_vertically(self.height);
+    }
+    result
+  }
+
+  ///
+  /// Maps the point from the canvas back to the data domain, e.g. for hit-testing.
+  pub fn invert(&self, p: &Point) -> Point {
+    let mut p = p.clone();
+    if self.flip_y {
+      p.invert_vertically(self.height);
+    }
+
+    let rx = ratio(p.x - self.padding, self.inner_width());
+    let ry = ratio(p.y - self.padding, self.inner_height());
+    Point {
+      x: self.x_scale.value_at(rx, self.x_domain),
+      y: self.y_scale.value_at(ry, self.y_domain),
+      tension: p.tension,
+    }
+  }
+
+  ///
+  /// Maps all points from the data domain to the canvas.
+  pub fn map_points(&self, points: &Points) -> Points {
+    Points::from(
+      points
+        .get_ref()
+        .iter()
+        .map(|p| self.map(p))
+        .collect::<Vec<_>>(),
+    )
+  }
+
+  ///
+  /// Maps all points from the canvas back to the data domain.
+  pub fn invert_points(&self, points: &Points) -> Points {
+    Points::from(
+      points
+        .get_ref()
+        .iter()
+        .map(|p| self.invert(p))
+        .collect::<Vec<_>>(),
+    )
+  }
+
+  ///
+  /// Returns the same mapping as affine [`Transform`].
+  /// Returns `None` for logarithmic scale or for a domain of zero size.
+  ///
+  /// ```
+  /// use cubic_spline::{Point, Viewport};
+  ///
+  /// let viewport = Viewport::new(100.0, 100.0).x_domain(-1.0, 1.0);
+  /// let t = viewport.to_transform().unwrap();
+  ///
+  /// let p = Point::new(0.5, 0.25);
+  /// assert!(t.apply(&p).approx_eq(&viewport.map(&p)));
+  /// ```
+  ///
+  /// [`Transform`]: struct.Transform.html
+  pub fn to_transform(&self) -> Option<Transform> {
+    if self.x_scale == Scale::Log || self.y_scale == Scale::Log {
+      return None;
+    }
+
+    let origin = self.map(&Point::new(self.x_domain.0, self.y_domain.0));
+    let t = Transform::translate(-self.x_domain.0, -self.y_domain.0);
+    let (sx, sy) = (
+      linear_factor(self.x_domain, self.inner_width()),
+      linear_factor(self.y_domain, self.inner_height()),
+    );
+    let sy = if self.flip_y { -sy } else { sy };
+
+    if sx == 0.0 || sy == 0.0 {
+      return None;
+    }
+    Some(t.then_scale(sx, sy).then_translate(origin.x, origin.y))
+  }
+
+  fn inner_width(&self) -> f64 {
+    self.width - 2.0 * self.padding
+  }
+
+  fn inner_height(&self) -> f64 {
+    self.height - 2.0 * self.padding
+  }
+}
+
+fn ratio(v: f64, size: f64) -> f64 {
+  if size == 0.0 {
+    0.5
+  } else {
+    v / size
+  }
+}
+
+fn linear_factor((min, max): (f64, f64), size: f64) -> f64 {
+  if max == min {
+    0.0
+  } else {
+    size / (max - min)
+  }
+}
+
+fn domain_of<I: Iterator<Item = f64>>(values: I, scale: Scale) -> Option<(f64, f64)> {
+  let domain = values
+    .filter(|v| v.is_finite() && (scale != Scale::Log || *v > 0.0))
+    .fold(None, |acc, v| match acc {
+      None => Some((v, v)),
+      Some((min, max)) => Some((f64::min(min, v), f64::max(max, v))),
+    });
+
+  match (domain, scale) {
+    (Some(d), Scale::Time) => Some(nice_time_domain(d)),
+    _ => domain,
+  }
+}
+
+const SECOND: f64 = 1000.0;
+const MINUTE: f64 = 60.0 * SECOND;
+const HOUR: f64 = 60.0 * MINUTE;
+const DAY: f64 = 24.0 * HOUR;
+
+// Шаги короче суток; дальше идут сутки, умноженные на 1, 2, 5, 10, 20, ...
+const TIME_STEPS: [f64; 15] = [
+  1.0,
+  10.0,
+  100.0,
+  SECOND,
+  5.0 * SECOND,
+  15.0 * SECOND,
+  30.0 * SECOND,
+  MINUTE,
+  5.0 * MINUTE,
+  15.0 * MINUTE,
+  30.0 * MINUTE,
+  HOUR,
+  3.0 * HOUR,
+  6.0 * HOUR,
+  12.0 * HOUR,
+];
+
+const MAX_TIME_TICKS: f64 = 10.0;
+
+// Наименьший шаг, которым домен покрывается не более чем за `MAX_TIME_TICKS` шагов
+fn time_step(span: f64) -> f64 {
+  if let Some(step) = TIME_STEPS.iter().find(|s| span / **s <= MAX_TIME_TICKS) {
+    return *step;
+  }
+
+  let mut days = 1.0;
+  loop {
+    for m in &[1.0, 2.0, 5.0] {
+      let step = m * days * DAY;
+      if span / step <= MAX_TIME_TICKS {
+        return step;
+      }
+    }
+    days *= 10.0;
+  }
+}
+
+// Расширяет домен до целых шагов, отсчитанных от начала эпохи (UTC)
+fn nice_time_domain((min, max): (f64, f64)) -> (f64, f64) {
+  if min == max {
+    return (min, max);
+  }
+
+  let step = time_step(max - min);
+  ((min / step).floor() * step, (max / step).ceil() * step)
+}